@@ -0,0 +1,106 @@
+//! Generates two files for the crate to include:
+//!
+//! - `sample_tests.rs` turns `data/samples.toml` into one test per sample
+//!   answer, so adding a sample is a change to the manifest rather than to
+//!   the code. The tests are included by the `days` module and run through
+//!   the registry.
+//! - `embedded_inputs.rs` lists the puzzle inputs and answers files in
+//!   `data/` and in each profile directory inside it, and the sample
+//!   manifest, with their contents, when the `embed-inputs` feature is on,
+//!   and nothing otherwise.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/manifest.rs"]
+mod manifest;
+
+use manifest::{parse, MANIFEST_PATH};
+
+const DATA_PATH: &str = "data";
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    fs::write(Path::new(&out_dir).join("sample_tests.rs"), sample_tests()).unwrap();
+    fs::write(
+        Path::new(&out_dir).join("embedded_inputs.rs"),
+        embedded_inputs(),
+    )
+    .unwrap();
+}
+
+fn sample_tests() -> String {
+    println!("cargo:rerun-if-changed={MANIFEST_PATH}");
+
+    let source = fs::read_to_string(MANIFEST_PATH).unwrap_or_default();
+    let entries = parse(&source).unwrap_or_else(|error| panic!("{MANIFEST_PATH}: {error}"));
+
+    let mut tests = String::new();
+    for entry in entries {
+        let name = entry
+            .sample
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        let part = if entry.part == 1 { "One" } else { "Two" };
+
+        writeln!(
+            tests,
+            "#[test]\nfn data_{name}_part_{}() {{\n    check_sample({}, {:?}, Part::{part}, {:?});\n}}\n",
+            entry.part, entry.day, entry.sample, entry.expected
+        )
+        .unwrap();
+    }
+
+    tests
+}
+
+/// A slice of `(name, contents)` for every data file in `data/` or a profile
+/// directory in it, that is every file whose name starts with a day number,
+/// every `answers.toml` and `samples.toml`. Names are relative to `data/`,
+/// such as `5`, `alice/5` or `alice/answers.toml`.
+fn embedded_inputs() -> String {
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return String::from("&[]");
+    }
+    println!("cargo:rerun-if-changed={DATA_PATH}");
+
+    let mut names = input_files(Path::new(DATA_PATH), "");
+    for entry in fs::read_dir(DATA_PATH).unwrap() {
+        let entry = entry.unwrap();
+        if entry.path().is_dir() {
+            let profile = entry.file_name().into_string().unwrap();
+            println!("cargo:rerun-if-changed={}", entry.path().display());
+            names.extend(input_files(&entry.path(), &format!("{profile}/")));
+        }
+    }
+    names.sort();
+
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let mut inputs = String::from("&[\n");
+    for name in names {
+        let path = Path::new(&root).join(DATA_PATH).join(&name);
+        writeln!(inputs, "    ({name:?}, include_str!({path:?})),").unwrap();
+    }
+    inputs.push(']');
+    inputs
+}
+
+/// The data files directly in `dir`, named with `prefix`.
+fn input_files(dir: &Path, prefix: &str) -> Vec<String> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap())
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().into_string().unwrap())
+        .filter(|name| {
+            name.starts_with(|c: char| c.is_ascii_digit())
+                || name == "answers.toml"
+                || name == "samples.toml"
+        })
+        .map(|name| format!("{prefix}{name}"))
+        .collect()
+}
@@ -0,0 +1,177 @@
+//! Allocation counting for the `count-allocations` feature. The counters are
+//! per thread, so days solved in parallel don't see each other's
+//! allocations.
+
+#[cfg(feature = "count-allocations")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Whether allocations are being counted in this build.
+pub const ENABLED: bool = cfg!(feature = "count-allocations");
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// The system allocator, keeping count of what each thread allocates.
+#[cfg(feature = "count-allocations")]
+pub struct Counting;
+
+#[derive(Clone, Copy)]
+struct Counters {
+    count: u64,
+    bytes: u64,
+    live: u64,
+    peak: u64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            count: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+/// Update this thread's counters. Allocations made while the thread is being
+/// torn down go uncounted.
+#[cfg(feature = "count-allocations")]
+fn update(f: impl FnOnce(&mut Counters)) {
+    let _ = COUNTERS.try_with(|counters| {
+        let mut current = counters.get();
+        f(&mut current);
+        counters.set(current);
+    });
+}
+
+#[cfg(feature = "count-allocations")]
+fn allocated(size: usize) {
+    update(|counters| {
+        counters.count += 1;
+        counters.bytes += size as u64;
+        counters.live += size as u64;
+        counters.peak = counters.peak.max(counters.live);
+    });
+}
+
+#[cfg(feature = "count-allocations")]
+fn freed(size: usize) {
+    update(|counters| counters.live = counters.live.saturating_sub(size as u64));
+}
+
+#[cfg(feature = "count-allocations")]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_pointer
+    }
+}
+
+/// What one phase allocated.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct Allocations {
+    /// Number of allocations, counting each reallocation as one.
+    pub count: u64,
+    /// Total bytes allocated, whether or not they were freed again.
+    pub bytes: u64,
+    /// The most bytes live at once, over what was live when it started.
+    pub peak: u64,
+}
+
+/// Run `f`, counting what it allocates on this thread. Returns `None` for the
+/// allocations unless the `count-allocations` feature is on.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+
+    let mut before = COUNTERS.with(Cell::get);
+    before.peak = before.live;
+    COUNTERS.with(|counters| counters.set(before));
+
+    let result = f();
+    let after = COUNTERS.with(Cell::get);
+
+    let allocations = Allocations {
+        count: after.count - before.count,
+        bytes: after.bytes - before.bytes,
+        peak: after.peak - before.live,
+    };
+    (result, Some(allocations))
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1 << 10 {
+        format!("{bytes}B")
+    } else if bytes < 1 << 20 {
+        format!("{:.2}KiB", bytes as f64 / (1u64 << 10) as f64)
+    } else if bytes < 1 << 30 {
+        format!("{:.2}MiB", bytes as f64 / (1u64 << 20) as f64)
+    } else {
+        format!("{:.2}GiB", bytes as f64 / (1u64 << 30) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (length, allocations) = measure(|| {
+            let small = vec![0_u8; 100];
+            drop(small);
+            let large = vec![0_u8; 1000];
+            large.len()
+        });
+
+        assert_eq!(length, 1000);
+        if ENABLED {
+            assert_eq!(
+                allocations,
+                Some(Allocations {
+                    count: 2,
+                    bytes: 1100,
+                    peak: 1000,
+                })
+            );
+        } else {
+            assert_eq!(allocations, None);
+        }
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.50KiB");
+        assert_eq!(format_bytes(3 << 20), "3.00MiB");
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use aoc_2023::solution::{Answer, Part};
+use aoc_2023::utils::read_data_file;
+
+pub const ANSWERS_PATH: &str = "data/answers.toml";
+
+/// Written at the top of every saved answers file, since saving regenerates
+/// it from the parsed answers alone.
+const HEADER: &str = "# Confirmed answers, rewritten by `aoc-2023 record` and `aoc-2023 submit`.
+# Edit the answers freely, but comments other than this one aren't kept.
+
+";
+
+/// Confirmed answers for the real inputs, stored as a small TOML file with a
+/// table per day:
+///
+/// ```toml
+/// [5]
+/// part_1 = "174137457"
+/// part_2 = "1493866"
+/// ```
+#[derive(PartialEq, Debug, Default)]
+pub struct KnownAnswers {
+    answers: BTreeMap<(u8, Part), String>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl KnownAnswers {
+    /// Load the answers file, or the copy embedded by the `embed-inputs`
+    /// feature, treating a missing file as having no answers.
+    pub fn load(path: &Path) -> Result<KnownAnswers, String> {
+        match read_data_file(path) {
+            Ok(source) => KnownAnswers::parse(&source),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(KnownAnswers::default()),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    /// Load the answers file like [`KnownAnswers::load`], but fail if there
+    /// isn't one.
+    pub fn load_existing(path: &Path) -> Result<KnownAnswers, String> {
+        let source = read_data_file(path).map_err(|error| error.to_string())?;
+        KnownAnswers::parse(&source)
+    }
+
+    pub fn parse(source: &str) -> Result<KnownAnswers, String> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            let line_number = index + 1;
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                day = Some(
+                    header
+                        .trim()
+                        .parse::<u8>()
+                        .map_err(|_| format!("line {line_number}: '{header}' is not a day"))?,
+                );
+                continue;
+            }
+
+            let day = day.ok_or(format!(
+                "line {line_number}: answer outside of a [day] table"
+            ))?;
+            let (key, value) = line.split_once('=').ok_or(format!(
+                "line {line_number}: expected 'part_N = \"answer\"'"
+            ))?;
+            let part = key
+                .trim()
+                .strip_prefix("part_")
+                .ok_or(format!("line {line_number}: unknown key '{}'", key.trim()))?
+                .parse::<Part>()
+                .map_err(|error| format!("line {line_number}: {error}"))?;
+            let value = value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .ok_or(format!("line {line_number}: answers must be quoted"))?;
+
+            answers.insert((day, part), String::from(value));
+        }
+
+        Ok(KnownAnswers { answers })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, format!("{HEADER}{self}"))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: &Answer) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: String::from(expected),
+            },
+        }
+    }
+}
+
+impl Display for KnownAnswers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut current_day = None;
+
+        for ((day, part), answer) in &self.answers {
+            if current_day != Some(*day) {
+                if current_day.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[{day}]")?;
+                current_day = Some(*day);
+            }
+            writeln!(f, "part_{part} = \"{answer}\"")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# Confirmed answers
+[1]
+part_1 = \"142\"
+part_2 = \"281\"
+
+[10]
+part_1 = \"8\"
+";
+
+    #[test]
+    fn test_parse() {
+        let answers = KnownAnswers::parse(SAMPLE).unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some("142"));
+        assert_eq!(answers.get(1, Part::Two), Some("281"));
+        assert_eq!(answers.get(10, Part::One), Some("8"));
+        assert_eq!(answers.get(10, Part::Two), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let answers = KnownAnswers::parse(SAMPLE).unwrap();
+        let written = answers.to_string();
+
+        assert_eq!(
+            written,
+            "[1]\npart_1 = \"142\"\npart_2 = \"281\"\n\n[10]\npart_1 = \"8\"\n"
+        );
+        assert_eq!(KnownAnswers::parse(&written).unwrap(), answers);
+        assert_eq!(
+            KnownAnswers::parse(&format!("{HEADER}{written}")).unwrap(),
+            answers
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(KnownAnswers::parse("part_1 = \"1\"").is_err());
+        assert!(KnownAnswers::parse("[one]").is_err());
+        assert!(KnownAnswers::parse("[1]\npart_3 = \"1\"").is_err());
+        assert!(KnownAnswers::parse("[1]\npart_1 = 1").is_err());
+        assert!(KnownAnswers::parse("[1]\nanswer = \"1\"").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let mut answers = KnownAnswers::default();
+        answers.insert(4, Part::One, &Answer::from(13_u32));
+
+        assert_eq!(answers.check(4, Part::One, &13.into()), Verdict::Pass);
+        assert_eq!(
+            answers.check(4, Part::One, &14.into()),
+            Verdict::Fail {
+                expected: String::from("13")
+            }
+        );
+        assert_eq!(answers.check(4, Part::Two, &30.into()), Verdict::Missing);
+    }
+}
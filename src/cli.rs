@@ -0,0 +1,863 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_2023::error::{Error, ParseError};
+use aoc_2023::solution::Part;
+
+use crate::profiles::Profile;
+use crate::report::Format;
+
+pub const USAGE: &str = "Usage: aoc-2023 [COMMAND]
+
+Commands:
+  run <DAY|all> [OPTIONS]  Solve one day, or every registered day (default: run all)
+  bench [DAY|all] [OPTIONS]
+                           Time the parse, part 1 and part 2 phases of each day
+  verify [DAY|all] [--profile <NAME>]
+                           Check answers for the real inputs against data/answers.toml
+  record <DAY|all> [--part <1|2>] [--profile <NAME>]
+                           Save the current answers to data/answers.toml once confirmed
+  new <DAY>                Create src/days/day_<DAY>.rs from the template, register it
+                           and add empty data/<DAY> and data/<DAY>_sample_1 files
+  fetch <DAY>              Download the puzzle input into data/<DAY> unless it's cached
+  submit <DAY> <1|2>       Post the answer for the real input, refusing answers that
+                           data/guesses.log already rules out or while the site
+                           has asked to wait
+  samples <DAY> <PAGE>     Save the examples in a saved puzzle page as data/<DAY>_sample_<k>
+                           and propose data/samples.toml entries for its answers
+  watch <DAY> [--once]     Check the day's samples and real input, and again whenever
+                           its source or data files change (--once: check and exit)
+  readme                   Rewrite the results table in README.md from the verified
+                           answers and measured times for the real inputs
+  help                     Print this message
+
+Run options:
+  --part <1|2>             Only print the given part
+  --input <PATH>           Read the puzzle input from PATH instead of data/<DAY>
+  --stdin                  Read the puzzle input from standard input
+  --profile <NAME>         Read data/<NAME>/<DAY> and check against data/<NAME>/answers.toml
+  --all-profiles           Solve the default and every named profile's inputs side by side
+  --strict                 Reject CRLF line endings, trailing whitespace and
+                           stray blank lines instead of cleaning them up
+  --format <FORMAT>        Print results as text (default), json, csv or tsv, with
+                           the input path, timings and known-answer check per part
+  --jobs <N>               Solve up to N days at once, still reporting them in
+                           order (default: 1, one day at a time)
+  --fetch                  Download any missing inputs into data/ before solving
+
+Site settings, for fetch, submit and --fetch, come from aoc.toml in the working
+directory (or the file named by AOC_CONFIG), with environment variables taking
+precedence:
+  session = \"<TOKEN>\"       AOC_SESSION    Session cookie for the puzzle site
+  base_url = \"<URL>\"        AOC_BASE_URL   Site to talk to (default: https://adventofcode.com)
+  contact = \"<EMAIL>\"       AOC_CONTACT    Contact sent in the User-Agent header
+
+Bench options:
+  --runs <N>               Timed runs per phase (default: 10)
+  --warmup <N>             Untimed runs per phase before timing (default: 2)
+  --profile <NAME>         Time data/<NAME>/<DAY> instead of data/<DAY>
+
+Profiles are extra sets of real inputs, such as one per account, kept in data/<NAME>/";
+
+#[derive(PartialEq, Debug)]
+pub enum Command {
+    Run(RunArgs),
+    Bench(BenchArgs),
+    Verify(VerifyArgs),
+    Record(RecordArgs),
+    New(NewArgs),
+    Fetch(FetchArgs),
+    Submit(SubmitArgs),
+    Samples(SamplesArgs),
+    Watch(WatchArgs),
+    Readme,
+    Help,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RunArgs {
+    pub days: DaySelection,
+    pub part: Option<Part>,
+    pub input: InputSource,
+    pub strict: bool,
+    pub format: Format,
+    pub jobs: usize,
+    pub fetch: bool,
+    /// Solve the inputs of every profile instead of `input`.
+    pub all_profiles: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct BenchArgs {
+    pub days: DaySelection,
+    pub runs: usize,
+    pub warmup: usize,
+    pub profile: Profile,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct VerifyArgs {
+    pub days: DaySelection,
+    pub profile: Profile,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RecordArgs {
+    pub days: DaySelection,
+    pub part: Option<Part>,
+    pub profile: Profile,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct NewArgs {
+    pub day: u8,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct FetchArgs {
+    pub day: u8,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct SubmitArgs {
+    pub day: u8,
+    pub part: Part,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct SamplesArgs {
+    pub day: u8,
+    pub page: PathBuf,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct WatchArgs {
+    pub day: u8,
+    /// Check once and exit instead of watching.
+    pub once: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum DaySelection {
+    All,
+    One(u8),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum InputSource {
+    /// The real input in a profile.
+    Data(Profile),
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum CliError {
+    Usage(String),
+    UnknownDay(u8),
+    Input {
+        path: String,
+        reason: String,
+    },
+    Verification {
+        failed: usize,
+        answers: String,
+    },
+    Solution(Error),
+    AlreadyExists(String),
+    /// Several days failed during one command, in day order.
+    Failed(Vec<CliError>),
+    /// A day failed on the input of a named profile.
+    InProfile {
+        profile: String,
+        error: Box<CliError>,
+    },
+    Fetch(String),
+    Submit(String),
+}
+
+impl From<Error> for CliError {
+    fn from(error: Error) -> Self {
+        CliError::Solution(error)
+    }
+}
+
+impl From<ParseError> for CliError {
+    fn from(error: ParseError) -> Self {
+        CliError::Solution(Error::Parse(error))
+    }
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            CliError::Verification { .. } => ExitCode::from(1),
+            CliError::Usage(_) => ExitCode::from(2),
+            CliError::UnknownDay(_) => ExitCode::from(3),
+            CliError::Input { .. } => ExitCode::from(4),
+            CliError::Solution(_) => ExitCode::from(5),
+            CliError::AlreadyExists(_) => ExitCode::from(6),
+            CliError::Fetch(_) => ExitCode::from(7),
+            CliError::Submit(_) => ExitCode::from(8),
+            CliError::Failed(errors) => errors
+                .first()
+                .map_or(ExitCode::FAILURE, CliError::exit_code),
+            CliError::InProfile { error, .. } => error.exit_code(),
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{message}\n\n{USAGE}"),
+            CliError::UnknownDay(day) => write!(f, "Day {day} has no registered solution"),
+            CliError::Input { path, reason } => write!(f, "Unable to read '{path}': {reason}"),
+            CliError::Solution(error) => write!(f, "{error}"),
+            CliError::Verification { failed, answers } => {
+                write!(f, "{failed} answer(s) did not match {answers}")
+            }
+            CliError::AlreadyExists(what) => write!(f, "Refusing to overwrite: {what}"),
+            CliError::Fetch(reason) => write!(f, "Unable to fetch: {reason}"),
+            CliError::Submit(reason) => write!(f, "{reason}"),
+            CliError::Failed(errors) => {
+                let noun = if errors.len() == 1 { "day" } else { "days" };
+                write!(f, "{} {noun} failed:", errors.len())?;
+                errors.iter().try_for_each(|error| write!(f, "\n{error}"))
+            }
+            CliError::InProfile { profile, error } => write!(f, "Profile {profile}: {error}"),
+        }
+    }
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, CliError> {
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        None => Ok(Command::Run(RunArgs {
+            days: DaySelection::All,
+            part: None,
+            input: InputSource::Data(Profile::default()),
+            strict: false,
+            format: Format::Text,
+            jobs: 1,
+            fetch: false,
+            all_profiles: false,
+        })),
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some("verify") => parse_verify(args).map(Command::Verify),
+        Some("record") => parse_record(args).map(Command::Record),
+        Some("new") => parse_new(args).map(Command::New),
+        Some("fetch") => parse_fetch(args).map(Command::Fetch),
+        Some("submit") => parse_submit(args).map(Command::Submit),
+        Some("samples") => parse_samples(args).map(Command::Samples),
+        Some("watch") => parse_watch(args).map(Command::Watch),
+        Some("readme") => match args.next() {
+            None => Ok(Command::Readme),
+            Some(arg) => Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+        },
+        Some("help" | "--help" | "-h") => Ok(Command::Help),
+        Some(other) => Err(CliError::Usage(format!("Unknown command '{other}'"))),
+    }
+}
+
+fn parse_run<I: Iterator<Item = String>>(mut args: I) -> Result<RunArgs, CliError> {
+    let days = match args.next() {
+        None => return Err(CliError::Usage(String::from("Missing day to run"))),
+        Some(day) => parse_days(&day)?,
+    };
+    let mut part = None;
+    let mut input = InputSource::Data(Profile::default());
+    let mut strict = false;
+    let mut format = Format::Text;
+    let mut jobs = 1;
+    let mut fetch = false;
+    let mut all_profiles = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = expect_value(&mut args, "--part")?;
+                part = Some(value.parse().map_err(CliError::Usage)?);
+            }
+            "--input" => {
+                input = InputSource::File(PathBuf::from(expect_value(&mut args, "--input")?))
+            }
+            "--stdin" => input = InputSource::Stdin,
+            "--profile" => {
+                input = InputSource::Data(parse_profile(&expect_value(&mut args, "--profile")?)?)
+            }
+            "--all-profiles" => all_profiles = true,
+            "--strict" => strict = true,
+            "--format" => {
+                let value = expect_value(&mut args, "--format")?;
+                format = value.parse().map_err(CliError::Usage)?;
+            }
+            "--fetch" => fetch = true,
+            "--jobs" => jobs = parse_count(&expect_value(&mut args, "--jobs")?)?,
+            _ => return Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+        }
+    }
+
+    let named_profile = matches!(&input, InputSource::Data(profile) if !profile.is_default());
+    if days == DaySelection::All && !matches!(input, InputSource::Data(_)) {
+        return Err(CliError::Usage(String::from(
+            "--input and --stdin need a single day",
+        )));
+    }
+    if all_profiles && input != InputSource::Data(Profile::default()) {
+        return Err(CliError::Usage(String::from(
+            "--all-profiles can't be combined with --profile, --input or --stdin",
+        )));
+    }
+    if fetch && (all_profiles || named_profile) {
+        return Err(CliError::Usage(String::from(
+            "--fetch only downloads the default profile's inputs",
+        )));
+    }
+    if jobs == 0 {
+        return Err(CliError::Usage(String::from("--jobs must be at least 1")));
+    }
+
+    Ok(RunArgs {
+        days,
+        part,
+        input,
+        strict,
+        format,
+        jobs,
+        fetch,
+        all_profiles,
+    })
+}
+
+fn parse_bench<I: Iterator<Item = String>>(args: I) -> Result<BenchArgs, CliError> {
+    let mut args = args.peekable();
+    let days = match args.next_if(|arg| !arg.starts_with("--")) {
+        None => DaySelection::All,
+        Some(day) => parse_days(&day)?,
+    };
+    let mut runs = 10;
+    let mut warmup = 2;
+    let mut profile = Profile::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => runs = parse_count(&expect_value(&mut args, "--runs")?)?,
+            "--warmup" => warmup = parse_count(&expect_value(&mut args, "--warmup")?)?,
+            "--profile" => profile = parse_profile(&expect_value(&mut args, "--profile")?)?,
+            _ => return Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+        }
+    }
+
+    if runs == 0 {
+        return Err(CliError::Usage(String::from("--runs must be at least 1")));
+    }
+
+    Ok(BenchArgs {
+        days,
+        runs,
+        warmup,
+        profile,
+    })
+}
+
+fn parse_verify<I: Iterator<Item = String>>(args: I) -> Result<VerifyArgs, CliError> {
+    let mut args = args.peekable();
+    let days = match args.next_if(|arg| !arg.starts_with("--")) {
+        None => DaySelection::All,
+        Some(day) => parse_days(&day)?,
+    };
+    let mut profile = Profile::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--profile" => profile = parse_profile(&expect_value(&mut args, "--profile")?)?,
+            _ => return Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+        }
+    }
+
+    Ok(VerifyArgs { days, profile })
+}
+
+fn parse_record<I: Iterator<Item = String>>(mut args: I) -> Result<RecordArgs, CliError> {
+    let days = match args.next() {
+        None => return Err(CliError::Usage(String::from("Missing day to record"))),
+        Some(day) => parse_days(&day)?,
+    };
+    let mut part = None;
+    let mut profile = Profile::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = expect_value(&mut args, "--part")?;
+                part = Some(value.parse().map_err(CliError::Usage)?);
+            }
+            "--profile" => profile = parse_profile(&expect_value(&mut args, "--profile")?)?,
+            _ => return Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+        }
+    }
+
+    Ok(RecordArgs {
+        days,
+        part,
+        profile,
+    })
+}
+
+fn parse_new<I: Iterator<Item = String>>(mut args: I) -> Result<NewArgs, CliError> {
+    let day = parse_puzzle_day(args.next(), "create")?;
+
+    match args.next() {
+        None => Ok(NewArgs { day }),
+        Some(arg) => Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+    }
+}
+
+fn parse_fetch<I: Iterator<Item = String>>(mut args: I) -> Result<FetchArgs, CliError> {
+    let day = parse_puzzle_day(args.next(), "fetch")?;
+
+    match args.next() {
+        None => Ok(FetchArgs { day }),
+        Some(arg) => Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+    }
+}
+
+fn parse_submit<I: Iterator<Item = String>>(mut args: I) -> Result<SubmitArgs, CliError> {
+    let day = parse_puzzle_day(args.next(), "submit")?;
+    let part = args
+        .next()
+        .ok_or_else(|| CliError::Usage(String::from("Missing part to submit")))?
+        .parse()
+        .map_err(CliError::Usage)?;
+
+    match args.next() {
+        None => Ok(SubmitArgs { day, part }),
+        Some(arg) => Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+    }
+}
+
+fn parse_samples<I: Iterator<Item = String>>(mut args: I) -> Result<SamplesArgs, CliError> {
+    let day = parse_puzzle_day(args.next(), "extract samples for")?;
+    let page = args
+        .next()
+        .map(PathBuf::from)
+        .ok_or_else(|| CliError::Usage(String::from("Missing puzzle page to read")))?;
+
+    match args.next() {
+        None => Ok(SamplesArgs { day, page }),
+        Some(arg) => Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+    }
+}
+
+fn parse_watch<I: Iterator<Item = String>>(mut args: I) -> Result<WatchArgs, CliError> {
+    let day = parse_puzzle_day(args.next(), "watch")?;
+    let mut once = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--once" => once = true,
+            _ => return Err(CliError::Usage(format!("Unexpected argument '{arg}'"))),
+        }
+    }
+
+    Ok(WatchArgs { day, once })
+}
+
+/// A day of the event, whether or not it has a solution yet.
+fn parse_puzzle_day(value: Option<String>, action: &str) -> Result<u8, CliError> {
+    let value = value.ok_or_else(|| CliError::Usage(format!("Missing day to {action}")))?;
+
+    value
+        .parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| CliError::Usage(String::from("The day must be between 1 and 25")))
+}
+
+fn parse_count(value: &str) -> Result<usize, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError::Usage(format!("'{value}' is not a count")))
+}
+
+fn parse_days(value: &str) -> Result<DaySelection, CliError> {
+    if value == "all" {
+        return Ok(DaySelection::All);
+    }
+
+    value.parse().map(DaySelection::One).map_err(|_| {
+        CliError::Usage(format!(
+            "'{value}' is not a day, expected a number or 'all'"
+        ))
+    })
+}
+
+fn parse_profile(value: &str) -> Result<Profile, CliError> {
+    Profile::named(value).map_err(CliError::Usage)
+}
+
+fn expect_value<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<String, CliError> {
+    args.next()
+        .ok_or_else(|| CliError::Usage(format!("{flag} needs a value")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, CliError> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_no_args_runs_everything() {
+        assert_eq!(
+            parse(""),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::All,
+                part: None,
+                input: InputSource::Data(Profile::default()),
+                strict: false,
+                format: Format::Text,
+                jobs: 1,
+                fetch: false,
+                all_profiles: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_run_day_and_part() {
+        assert_eq!(
+            parse("run 5 --part 2"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(5),
+                part: Some(Part::Two),
+                input: InputSource::Data(Profile::default()),
+                strict: false,
+                format: Format::Text,
+                jobs: 1,
+                fetch: false,
+                all_profiles: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_run_input_sources() {
+        assert_eq!(
+            parse("run 7 --input data/7_sample_1"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(7),
+                part: None,
+                input: InputSource::File(PathBuf::from("data/7_sample_1")),
+                strict: false,
+                format: Format::Text,
+                jobs: 1,
+                fetch: false,
+                all_profiles: false,
+            }))
+        );
+        assert_eq!(
+            parse("run 12 --stdin"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(12),
+                part: None,
+                input: InputSource::Stdin,
+                strict: false,
+                format: Format::Text,
+                jobs: 1,
+                fetch: false,
+                all_profiles: false,
+            }))
+        );
+        assert_eq!(
+            parse("run 2 --strict"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(2),
+                part: None,
+                input: InputSource::Data(Profile::default()),
+                strict: true,
+                format: Format::Text,
+                jobs: 1,
+                fetch: false,
+                all_profiles: false,
+            }))
+        );
+        assert_eq!(
+            parse("run all --format csv"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::All,
+                part: None,
+                input: InputSource::Data(Profile::default()),
+                strict: false,
+                format: Format::Csv,
+                jobs: 1,
+                fetch: false,
+                all_profiles: false,
+            }))
+        );
+        assert_eq!(
+            parse("run all --jobs 4"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::All,
+                part: None,
+                input: InputSource::Data(Profile::default()),
+                strict: false,
+                format: Format::Text,
+                jobs: 4,
+                fetch: false,
+                all_profiles: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_bench_defaults() {
+        assert_eq!(
+            parse("bench"),
+            Ok(Command::Bench(BenchArgs {
+                days: DaySelection::All,
+                runs: 10,
+                warmup: 2,
+                profile: Profile::default(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_bench_options() {
+        assert_eq!(
+            parse("bench 12 --runs 50 --warmup 0"),
+            Ok(Command::Bench(BenchArgs {
+                days: DaySelection::One(12),
+                runs: 50,
+                warmup: 0,
+                profile: Profile::default(),
+            }))
+        );
+        assert_eq!(
+            parse("bench --runs 3"),
+            Ok(Command::Bench(BenchArgs {
+                days: DaySelection::All,
+                runs: 3,
+                warmup: 2,
+                profile: Profile::default(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_verify_and_record() {
+        assert_eq!(
+            parse("verify"),
+            Ok(Command::Verify(VerifyArgs {
+                days: DaySelection::All,
+                profile: Profile::default(),
+            }))
+        );
+        assert_eq!(
+            parse("verify 3"),
+            Ok(Command::Verify(VerifyArgs {
+                days: DaySelection::One(3),
+                profile: Profile::default(),
+            }))
+        );
+        assert_eq!(
+            parse("record 3 --part 1"),
+            Ok(Command::Record(RecordArgs {
+                days: DaySelection::One(3),
+                part: Some(Part::One),
+                profile: Profile::default(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(parse("new 14"), Ok(Command::New(NewArgs { day: 14 })));
+        assert!(matches!(parse("new"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("new 0"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("new 26"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("new 14 15"), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn test_fetch() {
+        assert_eq!(parse("fetch 3"), Ok(Command::Fetch(FetchArgs { day: 3 })));
+        assert!(matches!(parse("fetch"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("fetch all"), Err(CliError::Usage(_))));
+        assert_eq!(
+            parse("run 14 --fetch"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(14),
+                part: None,
+                input: InputSource::Data(Profile::default()),
+                strict: false,
+                format: Format::Text,
+                jobs: 1,
+                fetch: true,
+                all_profiles: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        assert_eq!(
+            parse("submit 7 2"),
+            Ok(Command::Submit(SubmitArgs {
+                day: 7,
+                part: Part::Two,
+            }))
+        );
+        assert!(matches!(parse("submit"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("submit all 1"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("submit 7"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("submit 7 3"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("submit 7 1 2"), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let alice = Profile::named("alice").unwrap();
+
+        assert_eq!(
+            parse("run 6 --profile alice"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::One(6),
+                part: None,
+                input: InputSource::Data(alice.clone()),
+                strict: false,
+                format: Format::Text,
+                jobs: 1,
+                fetch: false,
+                all_profiles: false,
+            }))
+        );
+        assert_eq!(
+            parse("run all --all-profiles"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::All,
+                part: None,
+                input: InputSource::Data(Profile::default()),
+                strict: false,
+                format: Format::Text,
+                jobs: 1,
+                fetch: false,
+                all_profiles: true,
+            }))
+        );
+        assert_eq!(
+            parse("verify --profile alice"),
+            Ok(Command::Verify(VerifyArgs {
+                days: DaySelection::All,
+                profile: alice.clone(),
+            }))
+        );
+        assert_eq!(
+            parse("record 6 --profile alice"),
+            Ok(Command::Record(RecordArgs {
+                days: DaySelection::One(6),
+                part: None,
+                profile: alice.clone(),
+            }))
+        );
+        assert_eq!(
+            parse("bench 6 --profile alice"),
+            Ok(Command::Bench(BenchArgs {
+                days: DaySelection::One(6),
+                runs: 10,
+                warmup: 2,
+                profile: alice,
+            }))
+        );
+
+        assert!(matches!(
+            parse("run 6 --profile ../x"),
+            Err(CliError::Usage(_))
+        ));
+        assert!(matches!(
+            parse("run 6 --all-profiles --stdin"),
+            Err(CliError::Usage(_))
+        ));
+        assert!(matches!(
+            parse("run 6 --profile alice --fetch"),
+            Err(CliError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn test_samples() {
+        assert_eq!(
+            parse("samples 8 day8.html"),
+            Ok(Command::Samples(SamplesArgs {
+                day: 8,
+                page: PathBuf::from("day8.html"),
+            }))
+        );
+        assert!(matches!(parse("samples 8"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("samples x a.html"), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn test_watch() {
+        assert_eq!(
+            parse("watch 8"),
+            Ok(Command::Watch(WatchArgs {
+                day: 8,
+                once: false,
+            }))
+        );
+        assert_eq!(
+            parse("watch 8 --once"),
+            Ok(Command::Watch(WatchArgs { day: 8, once: true }))
+        );
+        assert!(matches!(parse("watch"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("watch all"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("watch 8 --fast"), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn test_readme() {
+        assert_eq!(parse("readme"), Ok(Command::Readme));
+        assert!(matches!(parse("readme 8"), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        assert!(matches!(parse("run"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("run five"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("run 5 --part 3"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("run 5 --input"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("run all --stdin"), Err(CliError::Usage(_))));
+        assert!(matches!(
+            parse("run 5 --format xml"),
+            Err(CliError::Usage(_))
+        ));
+        assert!(matches!(parse("run all --jobs 0"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("bench --runs 0"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("bench --runs x"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("record"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("verify 3 4"), Err(CliError::Usage(_))));
+        assert!(matches!(parse("frobnicate"), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn test_failed_pluralises_days() {
+        let failed = |count| {
+            CliError::Failed(
+                (0..count)
+                    .map(|_| CliError::Fetch(String::from("offline")))
+                    .collect(),
+            )
+        };
+
+        assert!(failed(1).to_string().starts_with("1 day failed:"));
+        assert!(failed(2).to_string().starts_with("2 days failed:"));
+    }
+}
@@ -0,0 +1,282 @@
+use std::time::Duration;
+
+use aoc_2023::solution::Part;
+
+use crate::config::Config;
+use crate::guesses::Rejection;
+
+pub const YEAR: u16 = 2023;
+
+/// What the puzzle site said about a submitted answer.
+#[derive(PartialEq, Debug)]
+pub enum Outcome {
+    Correct,
+    /// A wrong answer, with how long the site asked to wait before the next
+    /// one if it said, such as "one minute".
+    Rejected(Rejection, Option<String>),
+    /// Submitted too soon after the last answer, with the site's own
+    /// description of how long is left, such as "1m 20s".
+    Wait(String),
+    /// The part was already solved, or isn't unlocked yet.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Read the verdict out of the page the site returns after a submission.
+    pub fn parse(page: &str) -> Result<Outcome, String> {
+        let article = page
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(page, |(article, _)| article);
+
+        if article.contains("That's the right answer") {
+            Ok(Outcome::Correct)
+        } else if article.contains("That's not the right answer") {
+            let rejection = if article.contains("too high") {
+                Rejection::TooHigh
+            } else if article.contains("too low") {
+                Rejection::TooLow
+            } else {
+                Rejection::Wrong
+            };
+            let wait = article
+                .split_once("Please wait ")
+                .or_else(|| article.split_once("please wait "))
+                .and_then(|(_, rest)| rest.split('.').next())
+                .map(|wait| String::from(wait.trim_end_matches(" before trying again")));
+            Ok(Outcome::Rejected(rejection, wait))
+        } else if article.contains("You gave an answer too recently") {
+            let left = article
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map_or("a little while", |(left, _)| left);
+            Ok(Outcome::Wait(String::from(left)))
+        } else if article.contains("You don't seem to be solving the right level") {
+            Ok(Outcome::WrongLevel)
+        } else {
+            Err(String::from(
+                "the response didn't say whether the answer was right",
+            ))
+        }
+    }
+}
+
+/// A minimal client for the puzzle site, authenticated by the session cookie.
+pub struct Client {
+    agent: ureq::Agent,
+    config: Config,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Client {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&config.user_agent())
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Client { agent, config }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/{YEAR}/day/{path}",
+            self.config.base_url.trim_end_matches('/')
+        )
+    }
+
+    fn session(&self) -> Result<&str, String> {
+        self.config.session.as_deref().ok_or_else(|| {
+            String::from("No session token, set AOC_SESSION or 'session' in aoc.toml")
+        })
+    }
+
+    /// Download the puzzle input for `day`.
+    pub fn input(&self, day: u8) -> Result<String, String> {
+        let response = self
+            .agent
+            .get(&self.url(&format!("{day}/input")))
+            .set("Cookie", &format!("session={}", self.session()?))
+            .call()
+            .map_err(|error| describe(error, day))?;
+
+        response
+            .into_string()
+            .map_err(|error| format!("Day {day}: unable to read the input: {error}"))
+    }
+
+    /// Post an answer for one part and report the site's verdict.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Outcome, String> {
+        let response = self
+            .agent
+            .post(&self.url(&format!("{day}/answer")))
+            .set("Cookie", &format!("session={}", self.session()?))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|error| describe(error, day))?;
+        let page = response
+            .into_string()
+            .map_err(|error| format!("Day {day}: unable to read the response: {error}"))?;
+
+        Outcome::parse(&page).map_err(|error| format!("Day {day}: {error}"))
+    }
+}
+
+fn describe(error: ureq::Error, day: u8) -> String {
+    match error {
+        ureq::Error::Status(404, _) => format!("Day {day} isn't unlocked yet"),
+        ureq::Error::Status(400 | 401 | 403 | 500, _) => format!(
+            "Day {day}: the site rejected the request, check that the session token is current"
+        ),
+        ureq::Error::Status(code, _) => format!("Day {day}: the site responded with {code}"),
+        ureq::Error::Transport(error) => format!("Day {day}: {error}"),
+    }
+}
+
+#[cfg(test)]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serve one canned HTTP response on a local port. The handle returns
+    /// the request line, headers and body that were received.
+    pub fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; length];
+            std::io::Read::read_exact(&mut reader, &mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(base_url: String, session: Option<&str>) -> Client {
+        Client::new(Config {
+            session: session.map(String::from),
+            base_url,
+            contact: Some(String::from("me@example.com")),
+        })
+    }
+
+    #[test]
+    fn test_input() {
+        let (base_url, server) = stub::serve_once("200 OK", "1abc2\npqr3stu8vwx\n");
+        let input = client(base_url, Some("abc123")).input(1);
+        let request = server.join().unwrap();
+
+        assert_eq!(input, Ok(String::from("1abc2\npqr3stu8vwx\n")));
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc123\r\n"));
+        assert!(request
+            .to_lowercase()
+            .contains("user-agent: aoc-2023/0.1.0 (by me@example.com)\r\n"));
+    }
+
+    #[test]
+    fn test_input_errors() {
+        let (base_url, server) = stub::serve_once("404 Not Found", "");
+        let error = client(base_url, Some("abc123")).input(25).unwrap_err();
+        server.join().unwrap();
+        assert_eq!(error, "Day 25 isn't unlocked yet");
+
+        let (base_url, server) = stub::serve_once("400 Bad Request", "");
+        let error = client(base_url, Some("expired")).input(3).unwrap_err();
+        server.join().unwrap();
+        assert!(error.contains("session token"));
+
+        let error = client(String::from("http://127.0.0.1:9"), None).input(3);
+        assert!(error.unwrap_err().starts_with("No session token"));
+    }
+
+    #[test]
+    fn test_submit() {
+        let page = "<main><article><p>That's the right answer!</p></article></main>";
+        let (base_url, server) = stub::serve_once("200 OK", page);
+        let outcome = client(base_url, Some("abc123")).submit(7, Part::Two, "5905");
+        let request = server.join().unwrap();
+
+        assert_eq!(outcome, Ok(Outcome::Correct));
+        assert!(request.starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=5905"));
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        let article = |text| format!("<html><article><p>{text}</p></article></html>");
+
+        assert_eq!(
+            Outcome::parse(&article(
+                "That's not the right answer; your answer is too high.  Please wait one minute."
+            )),
+            Ok(Outcome::Rejected(
+                Rejection::TooHigh,
+                Some(String::from("one minute"))
+            ))
+        );
+        assert_eq!(
+            Outcome::parse(&article(
+                "That's not the right answer; your answer is too low."
+            )),
+            Ok(Outcome::Rejected(Rejection::TooLow, None))
+        );
+        assert_eq!(
+            Outcome::parse(&article("That's not the right answer.  If you're stuck...")),
+            Ok(Outcome::Rejected(Rejection::Wrong, None))
+        );
+        assert_eq!(
+            Outcome::parse(&article(
+                "That's not the right answer.  Because you have guessed incorrectly 4 times \
+                 on this puzzle, please wait 5 minutes before trying again."
+            )),
+            Ok(Outcome::Rejected(
+                Rejection::Wrong,
+                Some(String::from("5 minutes"))
+            ))
+        );
+        assert_eq!(
+            Outcome::parse(&article(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 20s left to wait."
+            )),
+            Ok(Outcome::Wait(String::from("1m 20s")))
+        );
+        assert_eq!(
+            Outcome::parse(&article(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Ok(Outcome::WrongLevel)
+        );
+        assert!(Outcome::parse("<html>Puzzle inputs differ by user.</html>").is_err());
+    }
+}
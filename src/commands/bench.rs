@@ -0,0 +1,145 @@
+use std::hint::black_box;
+use std::time::Duration;
+
+use aoc_2023::days::Day;
+use aoc_2023::error::Error;
+use aoc_2023::solution::Part;
+use aoc_2023::utils::input::PuzzleInput;
+
+use crate::alloc::{self, format_bytes, Allocations};
+use crate::cli::{BenchArgs, CliError, InputSource};
+use crate::commands::run::{load_input, select_days};
+use crate::timing::{format_duration, measure, Stats};
+
+pub struct DayTimings {
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+    /// What each phase allocates, in the same order, when counted.
+    pub allocations: [Option<Allocations>; 3],
+}
+
+impl DayTimings {
+    fn total(&self) -> Duration {
+        self.parse.median + self.part_1.median + self.part_2.median
+    }
+}
+
+pub fn bench(args: &BenchArgs) -> Result<(), CliError> {
+    let mut results = vec![];
+
+    print!(
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "Day", "Phase", "Median", "p95", "Min", "Max"
+    );
+    if alloc::ENABLED {
+        print!("  {:>10}  {:>10}  {:>10}", "Allocs", "Bytes", "Peak");
+    }
+    println!();
+
+    for day in select_days(&args.days)? {
+        let source = PuzzleInput::new(&load_input(day, &InputSource::Data(args.profile.clone()))?);
+        let timings = bench_day(day, &source, args.warmup, args.runs)?;
+
+        let phases = [
+            ("parse", &timings.parse),
+            ("part 1", &timings.part_1),
+            ("part 2", &timings.part_2),
+        ];
+        for ((phase, stats), allocations) in phases.into_iter().zip(timings.allocations) {
+            print!(
+                "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>10}",
+                day.number,
+                phase,
+                format_duration(stats.median),
+                format_duration(stats.p95),
+                format_duration(stats.min),
+                format_duration(stats.max),
+            );
+            if let Some(allocations) = allocations {
+                print!(
+                    "  {:>10}  {:>10}  {:>10}",
+                    allocations.count,
+                    format_bytes(allocations.bytes),
+                    format_bytes(allocations.peak),
+                );
+            }
+            println!();
+        }
+
+        results.push((day.number, timings));
+    }
+
+    print_summary(&results);
+
+    Ok(())
+}
+
+/// Time each phase of a day separately, clearing any memoisation before
+/// every run so that cached days aren't flattered by earlier iterations.
+/// The day is solved once up front so that a broken input fails fast, and
+/// that cold run is the one whose allocations are counted.
+pub fn bench_day(
+    day: &Day,
+    source: &PuzzleInput,
+    warmup: usize,
+    runs: usize,
+) -> Result<DayTimings, Error> {
+    (day.reset)();
+    let (input, parse_allocations) = alloc::measure(|| day.parse(source));
+    let input = input?;
+    let mut allocations = [parse_allocations, None, None];
+    for (index, part) in Part::ALL.into_iter().enumerate() {
+        let (answer, part_allocations) = alloc::measure(|| day.solve(input.as_ref(), part));
+        answer?;
+        allocations[index + 1] = part_allocations;
+    }
+
+    let parse = sample(warmup, runs, || {
+        (day.reset)();
+        measure(|| black_box(day.parse(source))).1
+    });
+    let part = |part| {
+        sample(warmup, runs, || {
+            (day.reset)();
+            measure(|| black_box(day.solve(input.as_ref(), part))).1
+        })
+    };
+
+    Ok(DayTimings {
+        parse,
+        part_1: part(Part::One),
+        part_2: part(Part::Two),
+        allocations,
+    })
+}
+
+fn sample(warmup: usize, runs: usize, mut run: impl FnMut() -> Duration) -> Stats {
+    for _ in 0..warmup {
+        run();
+    }
+
+    let samples = (0..runs).map(|_| run()).collect::<Vec<_>>();
+    Stats::from_samples(&samples)
+}
+
+fn print_summary(results: &[(u8, DayTimings)]) {
+    println!(
+        "\n{:>3}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "Day", "Parse", "Part 1", "Part 2", "Total"
+    );
+
+    for (number, timings) in results {
+        println!(
+            "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}",
+            number,
+            format_duration(timings.parse.median),
+            format_duration(timings.part_1.median),
+            format_duration(timings.part_2.median),
+            format_duration(timings.total()),
+        );
+    }
+
+    let total: Duration = results.iter().map(|(_, timings)| timings.total()).sum();
+    println!("{:>3}  {:>46}", "All", format_duration(total));
+}
@@ -0,0 +1,46 @@
+use std::fs;
+
+use aoc_2023::utils;
+
+use crate::cli::{CliError, FetchArgs};
+use crate::client::Client;
+use crate::config::{Config, CONFIG_PATH};
+
+pub fn fetch(args: &FetchArgs) -> Result<(), CliError> {
+    let path = utils::input_path(&args.day.to_string());
+
+    if !fetch_input(&client()?, args.day)? {
+        eprintln!("{} is already cached", path.display());
+    }
+
+    Ok(())
+}
+
+pub fn client() -> Result<Client, CliError> {
+    Config::load()
+        .map(Client::new)
+        .map_err(|reason| CliError::Input {
+            path: String::from(CONFIG_PATH),
+            reason,
+        })
+}
+
+/// Download a day's input into `data/` unless it's already there, returning
+/// whether anything was downloaded. An empty file, as left by `new`, doesn't
+/// count as cached.
+pub fn fetch_input(client: &Client, day: u8) -> Result<bool, CliError> {
+    let path = utils::input_path(&day.to_string());
+
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(false);
+    }
+
+    let input = client.input(day).map_err(CliError::Fetch)?;
+    fs::write(&path, input).map_err(|error| CliError::Input {
+        path: path.display().to_string(),
+        reason: error.to_string(),
+    })?;
+    eprintln!("Downloaded {}", path.display());
+
+    Ok(true)
+}
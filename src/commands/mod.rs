@@ -0,0 +1,10 @@
+pub mod bench;
+pub mod fetch;
+pub mod new;
+pub mod readme;
+pub mod record;
+pub mod run;
+pub mod samples;
+pub mod submit;
+pub mod verify;
+pub mod watch;
@@ -0,0 +1,199 @@
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::Path;
+
+use aoc_2023::utils;
+
+use crate::cli::{CliError, NewArgs};
+
+const TEMPLATE_PATH: &str = "src/days/template";
+const DAYS_PATH: &str = "src/days/mod.rs";
+
+/// Create `src/days/day_N.rs` from the template, register it, and add empty
+/// input and sample files. Nothing is written if the day already exists.
+pub fn new(args: &NewArgs) -> Result<(), CliError> {
+    let day = args.day;
+    let source_path = format!("src/days/day_{day}.rs");
+
+    if Path::new(&source_path).exists() {
+        return Err(CliError::AlreadyExists(source_path));
+    }
+
+    let template = read(TEMPLATE_PATH)?;
+    let registry = read(DAYS_PATH)?;
+    let registry = register(&registry, day)?;
+
+    write(&source_path, &render(&template, day))?;
+    write(DAYS_PATH, &registry)?;
+    println!("Created {source_path}");
+
+    for name in [day.to_string(), format!("{day}_sample_1")] {
+        let path = utils::input_path(&name);
+        let display = path.display().to_string();
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => println!("Created {display}"),
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                println!("Kept existing {display}")
+            }
+            Err(error) => return Err(io_error(&display, error)),
+        }
+    }
+
+    println!("Add the sample answers to data/samples.toml to test them");
+
+    Ok(())
+}
+
+/// Fill in the day number wherever the template has `DayN`, `Day N` or
+/// `day/N`.
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("DayN", &format!("Day{day}"))
+        .replace("Day N", &format!("Day {day}"))
+        .replace("day/N", &format!("day/{day}"))
+}
+
+/// Add `pub mod day_N;` and the `DAYS` entry for `day` to the registry
+/// source, keeping both lists in the order the rest of the file uses.
+fn register(registry: &str, day: u8) -> Result<String, CliError> {
+    let module = format!("pub mod day_{day};");
+    let entry = format!("    day::<day_{day}::Day{day}>({day}),");
+    let lines = registry.lines().collect::<Vec<_>>();
+
+    if lines.contains(&module.as_str()) || lines.contains(&entry.as_str()) {
+        return Err(CliError::AlreadyExists(format!(
+            "day {day} is already registered in {DAYS_PATH}"
+        )));
+    }
+
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, line.strip_prefix("pub mod day_")?)))
+        .map(|(index, name)| (index, name.trim_end_matches(';')))
+        .collect::<Vec<_>>();
+    let last_module = modules
+        .last()
+        .ok_or_else(|| unexpected_registry("no day modules"))?;
+    let module_at = modules
+        .iter()
+        .find(|(_, name)| *name > day.to_string().as_str())
+        .map_or(last_module.0 + 1, |(index, _)| *index);
+
+    let days_start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS"))
+        .ok_or_else(|| unexpected_registry("no DAYS list"))?;
+    let days_end = lines[days_start..]
+        .iter()
+        .position(|line| *line == "];")
+        .map(|offset| days_start + offset)
+        .ok_or_else(|| unexpected_registry("the DAYS list is not closed"))?;
+    let entry_at = (days_start + 1..days_end)
+        .find(|&index| entry_number(lines[index]).is_some_and(|number| number > day))
+        .unwrap_or(days_end);
+
+    let mut lines = lines;
+    lines.insert(entry_at, &entry);
+    lines.insert(module_at, &module);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn unexpected_registry(reason: &str) -> CliError {
+    CliError::Input {
+        path: String::from(DAYS_PATH),
+        reason: String::from(reason),
+    }
+}
+
+fn entry_number(line: &str) -> Option<u8> {
+    line.trim()
+        .strip_suffix("),")?
+        .rsplit_once('(')?
+        .1
+        .parse()
+        .ok()
+}
+
+fn read(path: &str) -> Result<String, CliError> {
+    fs::read_to_string(path).map_err(|error| io_error(path, error))
+}
+
+fn write(path: &str, contents: &str) -> Result<(), CliError> {
+    fs::write(path, contents).map_err(|error| io_error(path, error))
+}
+
+fn io_error(path: &str, error: io::Error) -> CliError {
+    CliError::Input {
+        path: String::from(path),
+        reason: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "use std::any::Any;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+
+pub const DAYS: &[Day] = &[
+    day::<day_1::Day1>(1),
+    day::<day_2::Day2>(2),
+    day::<day_10::Day10>(10),
+];
+";
+
+    #[test]
+    fn test_render() {
+        let template = "//! [Day N](https://adventofcode.com/2023/day/N)\n\
+                        pub struct DayN;\nimpl Solution for DayN {}";
+        assert_eq!(
+            render(template, 14),
+            "//! [Day 14](https://adventofcode.com/2023/day/14)\n\
+             pub struct Day14;\nimpl Solution for Day14 {}"
+        );
+    }
+
+    #[test]
+    fn test_register_keeps_order() {
+        assert_eq!(
+            register(REGISTRY, 3).unwrap(),
+            "use std::any::Any;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod day_3;
+
+pub const DAYS: &[Day] = &[
+    day::<day_1::Day1>(1),
+    day::<day_2::Day2>(2),
+    day::<day_3::Day3>(3),
+    day::<day_10::Day10>(10),
+];
+"
+        );
+
+        let registry = register(REGISTRY, 14).unwrap();
+        assert!(registry.contains("pub mod day_10;\npub mod day_14;\npub mod day_2;"));
+        assert!(registry.contains("(10),\n    day::<day_14::Day14>(14),\n];"));
+    }
+
+    #[test]
+    fn test_register_errors() {
+        assert!(matches!(
+            register(REGISTRY, 2),
+            Err(CliError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            register("pub mod day_1;\n", 2),
+            Err(CliError::Input { .. })
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt::Write;
+use std::fs;
+use std::time::Duration;
+
+use aoc_2023::days::{Day, DAYS};
+use aoc_2023::solution::Part;
+use aoc_2023::utils::input::PuzzleInput;
+
+use crate::answers::{KnownAnswers, Verdict};
+use crate::cli::{CliError, InputSource};
+use crate::commands::bench::bench_day;
+use crate::commands::run::load_input;
+use crate::commands::verify::load_answers;
+use crate::profiles::Profile;
+use crate::timing::format_duration;
+
+const README_PATH: &str = "README.md";
+const MARKER: &str = "<!--- advent_readme_stars table --->";
+/// Untimed and timed runs per phase, as for `bench`, but fewer: the table
+/// only needs a representative median, not a distribution.
+const WARMUP: usize = 1;
+const RUNS: usize = 5;
+
+/// How one part fared on the real input.
+#[derive(PartialEq, Debug)]
+enum Status {
+    /// Matches the confirmed answer, taking this long to parse and solve.
+    Verified(Duration),
+    /// Solved, but there's no confirmed answer to check it against.
+    Unverified(Duration),
+    /// A wrong answer, or no answer at all.
+    Failed,
+    NoInput,
+}
+
+/// Solve every registered day on its real input and rewrite the results
+/// table in the README from what was verified.
+pub fn readme() -> Result<(), CliError> {
+    let known = load_answers(&Profile::default())?;
+    let results = DAYS
+        .iter()
+        .map(|day| (day.number, solve(day, &known)))
+        .collect::<Vec<_>>();
+
+    let source = fs::read_to_string(README_PATH).map_err(|error| CliError::Input {
+        path: String::from(README_PATH),
+        reason: error.to_string(),
+    })?;
+    let updated = replace_section(&source, &table(&results)).map_err(|reason| CliError::Input {
+        path: String::from(README_PATH),
+        reason,
+    })?;
+    fs::write(README_PATH, updated).map_err(|error| CliError::Input {
+        path: String::from(README_PATH),
+        reason: error.to_string(),
+    })?;
+
+    let verified = results
+        .iter()
+        .flat_map(|(_, statuses)| statuses)
+        .filter(|status| matches!(status, Status::Verified(_)))
+        .count();
+    println!(
+        "Updated {README_PATH}: {verified} of {} parts verified",
+        results.len() * 2
+    );
+
+    Ok(())
+}
+
+fn solve(day: &Day, known: &KnownAnswers) -> [Status; 2] {
+    let Ok(source) = load_input(day, &InputSource::Data(Profile::default())) else {
+        return [Status::NoInput, Status::NoInput];
+    };
+    if source.trim().is_empty() {
+        return [Status::NoInput, Status::NoInput];
+    }
+
+    let source = PuzzleInput::new(&source);
+    (day.reset)();
+    let Ok(input) = day.parse(&source) else {
+        return [Status::Failed, Status::Failed];
+    };
+    let Ok(timings) = bench_day(day, &source, WARMUP, RUNS) else {
+        return [Status::Failed, Status::Failed];
+    };
+
+    Part::ALL.map(|part| {
+        let Ok(answer) = day.solve(input.as_ref(), part) else {
+            return Status::Failed;
+        };
+        let time = timings.parse.median
+            + match part {
+                Part::One => timings.part_1.median,
+                Part::Two => timings.part_2.median,
+            };
+
+        match known.check(day.number, part, &answer) {
+            Verdict::Pass => Status::Verified(time),
+            Verdict::Missing => Status::Unverified(time),
+            Verdict::Fail { .. } => Status::Failed,
+        }
+    })
+}
+
+fn table(results: &[(u8, [Status; 2])]) -> String {
+    let mut out =
+        String::from("## 2023 Results\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n");
+
+    for (day, statuses) in results {
+        write!(
+            out,
+            "| [Day {day}](https://adventofcode.com/2023/day/{day}) |"
+        )
+        .unwrap();
+        for status in statuses {
+            let cell = match status {
+                Status::Verified(time) => format!("⭐ {}", format_duration(*time)),
+                Status::Unverified(time) => format!("❔ {}", format_duration(*time)),
+                Status::Failed => String::from("❌"),
+                Status::NoInput => String::from("—"),
+            };
+            write!(out, " {cell} |").unwrap();
+        }
+        out.push('\n');
+    }
+
+    out.push_str(
+        "\n⭐ matches `data/answers.toml`, ❔ not yet confirmed, ❌ wrong or failed. \
+         Times are the median of several warm runs of parsing and solving, as measured by `aoc-2023 readme`.\n",
+    );
+    out
+}
+
+/// Swap whatever is between the two markers for `section`.
+fn replace_section(readme: &str, section: &str) -> Result<String, String> {
+    let missing = || format!("expected a pair of '{MARKER}' lines");
+    let start = readme.find(MARKER).ok_or_else(missing)? + MARKER.len();
+    let end = start + readme[start..].find(MARKER).ok_or_else(missing)?;
+
+    Ok(format!("{}\n{section}{}", &readme[..start], &readme[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let results = [
+            (
+                1,
+                [
+                    Status::Verified(Duration::from_micros(120)),
+                    Status::Unverified(Duration::from_millis(3)),
+                ],
+            ),
+            (2, [Status::Failed, Status::NoInput]),
+        ];
+
+        assert_eq!(
+            table(&results).lines().skip(4).take(2).collect::<Vec<_>>(),
+            vec![
+                "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ 120.00µs | ❔ 3.00ms |",
+                "| [Day 2](https://adventofcode.com/2023/day/2) | ❌ | — |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_section() {
+        let readme = format!("# Title\n\n{MARKER}\nold\n{MARKER}\n\n## Build\n");
+
+        assert_eq!(
+            replace_section(&readme, "new\n"),
+            Ok(format!("# Title\n\n{MARKER}\nnew\n{MARKER}\n\n## Build\n"))
+        );
+        assert!(replace_section("# Title\n", "new\n").is_err());
+        assert!(replace_section(&format!("{MARKER}\nold\n"), "new\n").is_err());
+    }
+}
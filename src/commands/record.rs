@@ -0,0 +1,59 @@
+use aoc_2023::days::Day;
+use aoc_2023::solution::{Answer, Part};
+use aoc_2023::utils::input::PuzzleInput;
+
+use crate::cli::{CliError, InputSource, RecordArgs};
+use crate::commands::run::{load_input, select_days};
+use crate::commands::verify::load_answers;
+
+/// Store the current answers as confirmed, replacing any previous entry.
+/// Every selected day is solved before anything is written, and a day that
+/// fails doesn't stop the others from being recorded.
+pub fn record(args: &RecordArgs) -> Result<(), CliError> {
+    let mut known = load_answers(&args.profile)?;
+    let input = InputSource::Data(args.profile.clone());
+    let mut recorded = vec![];
+    let mut failures = vec![];
+
+    for day in select_days(&args.days)? {
+        match solve(day, &input, args.part) {
+            Ok(answers) => recorded.extend(answers),
+            Err(error) => failures.push(error),
+        }
+    }
+
+    for (day, part, answer) in &recorded {
+        known.insert(*day, *part, answer);
+    }
+    let path = args.profile.answers_path();
+    known.save(&path).map_err(|error| CliError::Input {
+        path: path.display().to_string(),
+        reason: error.to_string(),
+    })?;
+
+    for (day, part, answer) in &recorded {
+        println!("Day {day:>2}, Part {part}: recorded {answer}");
+    }
+
+    match failures.len() {
+        0 => Ok(()),
+        1 => Err(failures.remove(0)),
+        _ => Err(CliError::Failed(failures)),
+    }
+}
+
+/// The day's answers for the wanted parts, or the first error.
+fn solve(
+    day: &Day,
+    input: &InputSource,
+    wanted: Option<Part>,
+) -> Result<Vec<(u8, Part, Answer)>, CliError> {
+    let source = PuzzleInput::new(&load_input(day, input)?);
+    let input = day.parse(&source)?;
+
+    Part::ALL
+        .into_iter()
+        .filter(|&part| wanted.is_none_or(|wanted| wanted == part))
+        .map(|part| Ok((day.number, part, day.solve(input.as_ref(), part)?)))
+        .collect()
+}
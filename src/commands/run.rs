@@ -0,0 +1,213 @@
+use std::io;
+use std::path::Path;
+
+use aoc_2023::days::{self, Day};
+use aoc_2023::error::Error;
+use aoc_2023::solution::Part;
+use aoc_2023::utils::input::PuzzleInput;
+use aoc_2023::utils::{self, DATA_DIR};
+
+use crate::alloc;
+use crate::answers::KnownAnswers;
+use crate::cli::{CliError, DaySelection, InputSource, RunArgs};
+use crate::commands::fetch::{client, fetch_input};
+use crate::commands::verify::load_answers;
+use crate::parallel::{map_ordered, with_quiet_panics};
+use crate::profiles::Profile;
+use crate::report::{self, Format, Row};
+use crate::timing::measure;
+
+/// One day to solve on one input.
+struct Task<'a> {
+    day: &'static Day,
+    input: &'a InputSource,
+    known: Option<&'a KnownAnswers>,
+    /// The profile to name in the output, if any.
+    profile: Option<String>,
+    /// Whether this is the day's last input, after which text output is
+    /// printed.
+    last_for_day: bool,
+}
+
+pub fn run(args: &RunArgs) -> Result<(), CliError> {
+    let inputs = if args.all_profiles {
+        Profile::all()
+            .map_err(|reason| CliError::Input {
+                path: String::from(DATA_DIR),
+                reason,
+            })?
+            .into_iter()
+            .map(InputSource::Data)
+            .collect()
+    } else {
+        vec![args.input.clone()]
+    };
+
+    // Known answers only apply to the real inputs, and only the
+    // machine-readable formats report them.
+    let known = inputs
+        .iter()
+        .map(|input| match (args.format, input) {
+            (Format::Text, _) | (_, InputSource::File(_) | InputSource::Stdin) => Ok(None),
+            (_, InputSource::Data(profile)) => load_answers(profile).map(Some),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let days = select_days(&args.days)?;
+
+    if args.fetch && args.input == InputSource::Data(Profile::default()) {
+        let client = client()?;
+        for day in &days {
+            fetch_input(&client, day.number)?;
+        }
+    }
+
+    let tasks = days
+        .iter()
+        .flat_map(|day| {
+            inputs
+                .iter()
+                .zip(&known)
+                .enumerate()
+                .map(|(index, (input, known))| Task {
+                    day,
+                    input,
+                    known: known.as_ref(),
+                    profile: match input {
+                        InputSource::Data(profile)
+                            if args.all_profiles || !profile.is_default() =>
+                        {
+                            Some(profile.to_string())
+                        }
+                        _ => None,
+                    },
+                    last_for_day: index + 1 == inputs.len(),
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let mut rows = vec![];
+    let mut day_start = 0;
+    let mut failures = vec![];
+
+    // A failing day is reported once the others have had their turn.
+    with_quiet_panics(|| {
+        map_ordered(
+            &tasks,
+            args.jobs,
+            |task| run_day(task, args),
+            |task, result| {
+                let error = match result {
+                    Ok(Ok(task_rows)) => {
+                        rows.extend(task_rows);
+                        None
+                    }
+                    Ok(Err(error)) => Some(error),
+                    Err(panic) => Some(CliError::Solution(
+                        Error::solve(format!("panicked: {panic}")).in_day(task.day.number),
+                    )),
+                };
+                if let Some(error) = error {
+                    failures.push(match &task.profile {
+                        Some(profile) => CliError::InProfile {
+                            profile: profile.clone(),
+                            error: Box::new(error),
+                        },
+                        None => error,
+                    });
+                }
+
+                if task.last_for_day {
+                    if args.format == Format::Text {
+                        print!("{}", report::render(Format::Text, &rows[day_start..]));
+                    }
+                    day_start = rows.len();
+                }
+            },
+        )
+    });
+
+    if args.format != Format::Text {
+        print!("{}", report::render(args.format, &rows));
+    }
+
+    match failures.len() {
+        0 => Ok(()),
+        1 => Err(failures.remove(0)),
+        _ => Err(CliError::Failed(failures)),
+    }
+}
+
+fn run_day(task: &Task, args: &RunArgs) -> Result<Vec<Row>, CliError> {
+    let day = task.day;
+    let source = load_input(day, task.input)?;
+    let source = if args.strict {
+        PuzzleInput::strict(&source).map_err(|error| error.in_day(day.number))?
+    } else {
+        PuzzleInput::new(&source)
+    };
+    let ((input, parse_allocations), parse_time) =
+        measure(|| alloc::measure(|| day.parse(&source)));
+    let input = input?;
+    let mut rows = vec![];
+
+    for part in Part::ALL {
+        if args.part.is_some_and(|wanted| wanted != part) {
+            continue;
+        }
+        let ((answer, solve_allocations), solve_time) =
+            measure(|| alloc::measure(|| day.solve(input.as_ref(), part)));
+        let answer = answer?;
+
+        rows.push(Row {
+            day: day.number,
+            part,
+            known: task
+                .known
+                .map(|known| known.check(day.number, part, &answer)),
+            answer,
+            input: input_label(day, task.input),
+            profile: task.profile.clone(),
+            parse_time,
+            solve_time,
+            allocations: parse_allocations.zip(solve_allocations),
+        });
+    }
+
+    Ok(rows)
+}
+
+pub fn select_days(selection: &DaySelection) -> Result<Vec<&'static Day>, CliError> {
+    match selection {
+        DaySelection::All => Ok(days::DAYS.iter().collect()),
+        DaySelection::One(number) => Ok(vec![
+            days::find(*number).ok_or(CliError::UnknownDay(*number))?
+        ]),
+    }
+}
+
+pub fn load_input(day: &Day, input: &InputSource) -> Result<String, CliError> {
+    match input {
+        InputSource::Data(profile) => read_file(&profile.input_path(day.number)),
+        InputSource::File(path) => read_file(path),
+        InputSource::Stdin => io::read_to_string(io::stdin()).map_err(|error| CliError::Input {
+            path: String::from("<stdin>"),
+            reason: error.to_string(),
+        }),
+    }
+}
+
+/// Where a day's input came from, as shown in reports.
+fn input_label(day: &Day, input: &InputSource) -> String {
+    match input {
+        InputSource::Data(profile) => profile.input_path(day.number).display().to_string(),
+        InputSource::File(path) => path.display().to_string(),
+        InputSource::Stdin => String::from("<stdin>"),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, CliError> {
+    utils::try_read_input(path).map_err(|error| CliError::Input {
+        path: path.display().to_string(),
+        reason: error.to_string(),
+    })
+}
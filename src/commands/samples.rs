@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use aoc_2023::utils;
+
+use crate::cli::{CliError, SamplesArgs};
+use crate::samples::{expected_answers, extract_samples};
+
+/// Save the examples from a puzzle page as `data/<DAY>_sample_<k>` and
+/// propose manifest entries for the answers the page gives.
+pub fn samples(args: &SamplesArgs) -> Result<(), CliError> {
+    let day = args.day;
+    let page = fs::read_to_string(&args.page).map_err(|error| CliError::Input {
+        path: args.page.display().to_string(),
+        reason: error.to_string(),
+    })?;
+
+    for (index, sample) in extract_samples(&page).iter().enumerate() {
+        let path = utils::input_path(&format!("{day}_sample_{}", index + 1));
+
+        // Empty files, as left by `new`, are filled in; anything else is kept.
+        if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+            println!("Kept existing {}", path.display());
+            continue;
+        }
+        fs::write(&path, sample.trim_end_matches('\n')).map_err(|error| CliError::Input {
+            path: path.display().to_string(),
+            reason: error.to_string(),
+        })?;
+        println!("Created {}", path.display());
+    }
+
+    let mut by_sample = BTreeMap::<_, Vec<_>>::new();
+    for expected in expected_answers(&page) {
+        by_sample.entry(expected.sample).or_default().push(expected);
+    }
+
+    if !by_sample.is_empty() {
+        println!("\nProposed entries for data/samples.toml:");
+    }
+    for (sample, expected) in by_sample {
+        println!("\n[{day}_sample_{sample}]\nday = {day}");
+        for expected in expected {
+            println!("part_{} = \"{}\"", expected.part, expected.answer);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aoc_2023::days;
+use aoc_2023::utils::input::PuzzleInput;
+
+use crate::answers::ANSWERS_PATH;
+use crate::cli::{CliError, InputSource, SubmitArgs};
+use crate::client::Outcome;
+use crate::commands::fetch::client;
+use crate::commands::run::load_input;
+use crate::commands::verify::load_answers;
+use crate::guesses::{wait_seconds, GuessLog, GUESSES_PATH};
+use crate::profiles::Profile;
+
+/// Solve one part on the real input and post the answer, unless an earlier
+/// rejection already rules it out. Right answers are recorded in
+/// data/answers.toml, and wrong ones and any wait the site asks for in
+/// data/guesses.log.
+pub fn submit(args: &SubmitArgs) -> Result<(), CliError> {
+    let day = days::find(args.day).ok_or(CliError::UnknownDay(args.day))?;
+    let source = PuzzleInput::new(&load_input(day, &InputSource::Data(Profile::default()))?);
+    let input = day.parse(&source)?;
+    let answer = day.solve(input.as_ref(), args.part)?.to_string();
+
+    let mut known = load_answers(&Profile::default())?;
+    if let Some(confirmed) = known.get(day.number, args.part) {
+        return Err(CliError::Submit(format!(
+            "Not submitting: {confirmed} is already the confirmed answer in {ANSWERS_PATH}"
+        )));
+    }
+
+    let mut guesses =
+        GuessLog::load(Path::new(GUESSES_PATH)).map_err(|reason| CliError::Input {
+            path: String::from(GUESSES_PATH),
+            reason,
+        })?;
+    if let Some(reason) = guesses.rule_out(day.number, args.part, &answer) {
+        return Err(CliError::Submit(format!("Not submitting: {reason}")));
+    }
+    if let Some(left) = guesses.waiting(now()) {
+        return Err(CliError::Submit(format!(
+            "Not submitting: the site asked to wait another {left}s"
+        )));
+    }
+
+    println!(
+        "Day {}, Part {}: submitting {}",
+        day.number, args.part, answer
+    );
+    let outcome = client()?
+        .submit(day.number, args.part, &answer)
+        .map_err(CliError::Submit)?;
+
+    match outcome {
+        Outcome::Correct => {
+            println!("That's the right answer");
+            known.insert(day.number, args.part, &answer.as_str().into());
+            save(known.save(Path::new(ANSWERS_PATH)), ANSWERS_PATH)
+        }
+        Outcome::Rejected(rejection, wait) => {
+            guesses.insert(day.number, args.part, rejection, &answer);
+            if let Some(wait) = &wait {
+                guesses.wait_until(now() + wait_seconds(wait));
+            }
+            save(guesses.save(Path::new(GUESSES_PATH)), GUESSES_PATH)?;
+            Err(CliError::Submit(match wait {
+                Some(wait) => format!("{answer} was rejected as {rejection}, {wait} to wait"),
+                None => format!("{answer} was rejected as {rejection}"),
+            }))
+        }
+        Outcome::Wait(left) => {
+            guesses.wait_until(now() + wait_seconds(&left));
+            save(guesses.save(Path::new(GUESSES_PATH)), GUESSES_PATH)?;
+            Err(CliError::Submit(format!(
+                "Submitted too recently, {left} left to wait"
+            )))
+        }
+        Outcome::WrongLevel => Err(CliError::Submit(String::from(
+            "That part is already solved or not unlocked yet",
+        ))),
+    }
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+fn save(result: std::io::Result<()>, path: &str) -> Result<(), CliError> {
+    result.map_err(|error| CliError::Input {
+        path: String::from(path),
+        reason: error.to_string(),
+    })
+}
@@ -0,0 +1,91 @@
+use aoc_2023::error::ParseError;
+use aoc_2023::solution::Part;
+use aoc_2023::utils::input::PuzzleInput;
+
+use crate::answers::{KnownAnswers, Verdict};
+use crate::cli::{CliError, InputSource, VerifyArgs};
+use crate::commands::run::{load_input, select_days};
+use crate::profiles::Profile;
+
+pub fn verify(args: &VerifyArgs) -> Result<(), CliError> {
+    // Without an answers file every answer would count as missing and the
+    // check would pass, so that's an error here.
+    let path = args.profile.answers_path();
+    let known = KnownAnswers::load_existing(&path).map_err(|reason| CliError::Input {
+        path: path.display().to_string(),
+        reason,
+    })?;
+    let input = InputSource::Data(args.profile.clone());
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for day in select_days(&args.days)? {
+        // A day that can't be read or parsed counts against both its parts,
+        // and the rest still get checked.
+        let parsed = load_input(day, &input).map(|source| day.parse(&PuzzleInput::new(&source)));
+        let input = match parsed {
+            Ok(Ok(input)) => input,
+            Ok(Err(error)) => {
+                let error = ParseError { day: None, ..error };
+                for part in Part::ALL {
+                    failed += 1;
+                    println!("Day {:>2}, Part {}: ERROR   {}", day.number, part, error);
+                }
+                continue;
+            }
+            Err(error) => {
+                for part in Part::ALL {
+                    missing += 1;
+                    println!("Day {:>2}, Part {}: MISSING {}", day.number, part, error);
+                }
+                continue;
+            }
+        };
+
+        for part in Part::ALL {
+            let answer = match day.solve(input.as_ref(), part) {
+                Ok(answer) => answer,
+                Err(error) => {
+                    failed += 1;
+                    println!("Day {:>2}, Part {}: ERROR   {}", day.number, part, error);
+                    continue;
+                }
+            };
+            let status = match known.check(day.number, part, &answer) {
+                Verdict::Pass => {
+                    passed += 1;
+                    format!("PASS    {answer}")
+                }
+                Verdict::Fail { expected } => {
+                    failed += 1;
+                    format!("FAIL    expected {expected}, got {answer}")
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    format!("MISSING {answer}")
+                }
+            };
+            println!("Day {:>2}, Part {}: {}", day.number, part, status);
+        }
+    }
+
+    println!("\n{passed} passed, {failed} failed, {missing} missing");
+
+    if failed > 0 {
+        return Err(CliError::Verification {
+            failed,
+            answers: args.profile.answers_path().display().to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// The confirmed answers for a profile's real inputs.
+pub fn load_answers(profile: &Profile) -> Result<KnownAnswers, CliError> {
+    let path = profile.answers_path();
+
+    KnownAnswers::load(&path).map_err(|reason| CliError::Input {
+        path: path.display().to_string(),
+        reason,
+    })
+}
@@ -0,0 +1,217 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc_2023::days::{self, Day};
+use aoc_2023::solution::Part;
+use aoc_2023::utils::input::PuzzleInput;
+use aoc_2023::utils::{self, DATA_DIR};
+
+use crate::answers::ANSWERS_PATH;
+use crate::cli::{CliError, InputSource, WatchArgs};
+use crate::commands::run::load_input;
+use crate::commands::verify::load_answers;
+use crate::manifest::{self, MANIFEST_PATH};
+use crate::profiles::Profile;
+use crate::timing::{format_duration, measure};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Check a day's samples and real input, and again whenever its source or
+/// data files change. Each check runs in a fresh `cargo run`, so that edits
+/// to the solution are compiled in first.
+pub fn watch(args: &WatchArgs) -> Result<(), CliError> {
+    if args.once {
+        let day = days::find(args.day).ok_or(CliError::UnknownDay(args.day))?;
+        return check(day);
+    }
+
+    println!("Watching day {}, press Ctrl-C to stop", args.day);
+    let mut last = None;
+
+    loop {
+        let current = snapshot(args.day);
+        if last.as_ref() != Some(&current) {
+            println!();
+            recheck(args.day);
+            last = Some(current);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The solution, its inputs and the files that say what the answers are.
+fn watched_files(day: u8) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(format!("src/days/day_{day}.rs")),
+        PathBuf::from(MANIFEST_PATH),
+        PathBuf::from(ANSWERS_PATH),
+        utils::input_path(&day.to_string()),
+    ];
+
+    let prefix = format!("{day}_sample_");
+    let mut samples = fs::read_dir(DATA_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with(&prefix))
+        .collect::<Vec<_>>();
+    samples.sort();
+    files.extend(samples.iter().map(|name| utils::input_path(name)));
+
+    files
+}
+
+fn snapshot(day: u8) -> Vec<(PathBuf, Option<SystemTime>)> {
+    watched_files(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Rebuild and run `watch <day> --once` with the same profile and features
+/// as this binary. Build errors are cargo's to report.
+fn recheck(day: u8) {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command.args(["run", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    let features = enabled_features();
+    if !features.is_empty() {
+        command.args(["--features", &features.join(",")]);
+    }
+    command.args(["--", "watch", &day.to_string(), "--once"]);
+
+    if let Err(error) = command.status() {
+        eprintln!("Unable to run cargo: {error}");
+    }
+}
+
+/// The features this binary was built with, so that re-runs are built the
+/// same way.
+fn enabled_features() -> Vec<&'static str> {
+    [
+        ("embed-inputs", cfg!(feature = "embed-inputs")),
+        ("count-allocations", cfg!(feature = "count-allocations")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect()
+}
+
+/// A part to check, with its expected answer if there is one.
+type Expected<'a> = (Part, Option<&'a str>);
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+/// Solve every sample in the manifest for `day`, then the real input, with a
+/// line per part. Times cover parsing as well as solving the part.
+fn check(day: &Day) -> Result<(), CliError> {
+    let manifest = utils::read_data_file(Path::new(MANIFEST_PATH)).unwrap_or_default();
+    let entries = manifest::parse(&manifest).map_err(|reason| CliError::Input {
+        path: String::from(MANIFEST_PATH),
+        reason,
+    })?;
+    let known = load_answers(&Profile::default())?;
+    let mut tally = Tally::default();
+
+    let mut samples: Vec<(&str, Vec<Expected>)> = vec![];
+    for entry in entries.iter().filter(|entry| entry.day == day.number) {
+        let part = if entry.part == 1 {
+            Part::One
+        } else {
+            Part::Two
+        };
+        let expected = (part, Some(entry.expected.as_str()));
+
+        match samples
+            .iter_mut()
+            .find(|(sample, _)| *sample == entry.sample)
+        {
+            Some((_, parts)) => parts.push(expected),
+            None => samples.push((&entry.sample, vec![expected])),
+        }
+    }
+
+    for (sample, parts) in &samples {
+        check_input(day, &utils::input_path(sample), parts, &mut tally);
+    }
+
+    let real = Profile::default().input_path(day.number);
+    if fs::metadata(&real).is_ok_and(|metadata| metadata.len() > 0) {
+        let parts = Part::ALL.map(|part| (part, known.get(day.number, part)));
+        check_input(day, &real, &parts, &mut tally);
+    } else {
+        println!("{:<16}  no input yet", real.display());
+    }
+
+    println!(
+        "Day {}: {} passed, {} failed, {} missing",
+        day.number, tally.passed, tally.failed, tally.missing
+    );
+
+    Ok(())
+}
+
+fn check_input(day: &Day, path: &Path, parts: &[Expected], tally: &mut Tally) {
+    let label = path.display().to_string();
+    (day.reset)();
+
+    let input = load_input(day, &InputSource::File(path.to_path_buf())).and_then(|source| {
+        let (input, parse_time) = measure(|| day.parse(&PuzzleInput::new(&source)));
+        Ok((input?, parse_time))
+    });
+    let (input, parse_time) = match input {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            tally.failed += parts.len();
+            println!("{label:<16}  ERROR    {error}");
+            return;
+        }
+    };
+
+    for (part, expected) in parts {
+        let (answer, solve_time) = measure(|| day.solve(input.as_ref(), *part));
+        let time = format_duration(parse_time + solve_time);
+
+        let answer = match answer {
+            Ok(answer) => answer,
+            Err(error) => {
+                tally.failed += 1;
+                println!("{label:<16}  part {part}  ERROR    {error}");
+                continue;
+            }
+        };
+        let status = match expected {
+            Some(expected) if *expected == answer.to_string() => {
+                tally.passed += 1;
+                format!("PASS     {answer}")
+            }
+            Some(expected) => {
+                tally.failed += 1;
+                format!("FAIL     {answer}, expected {expected}")
+            }
+            None => {
+                tally.missing += 1;
+                format!("MISSING  {answer}")
+            }
+        };
+        println!("{label:<16}  part {part}  {status:<32}  {time:>10}");
+    }
+}
@@ -0,0 +1,141 @@
+use std::env;
+use std::fs;
+use std::io;
+
+pub const CONFIG_PATH: &str = "aoc.toml";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the puzzle site, read from `aoc.toml` in the
+/// working directory with environment variables taking precedence:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."   # or AOC_SESSION
+/// base_url = "http://127.0.0.1:8080" # or AOC_BASE_URL
+/// contact = "you@example.com"        # or AOC_CONTACT, sent in the User-Agent
+/// ```
+///
+/// `AOC_CONFIG` names a different file. The file is optional, and is kept
+/// out of git since it holds the session token.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub contact: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: String::from(DEFAULT_BASE_URL),
+            contact: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Config, String> {
+        let path = env::var("AOC_CONFIG").unwrap_or_else(|_| String::from(CONFIG_PATH));
+        let mut config = match fs::read_to_string(&path) {
+            Ok(source) => Config::parse(&source).map_err(|error| format!("{path}: {error}"))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(error) => return Err(format!("{path}: {error}")),
+        };
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+        if let Ok(contact) = env::var("AOC_CONTACT") {
+            config.contact = Some(contact);
+        }
+
+        Ok(config)
+    }
+
+    pub fn parse(source: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let line = match line.split_once(" #") {
+                Some((before, _)) => before.trim(),
+                None => line.trim(),
+            };
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("line {line_number}: expected 'key = \"value\"'"))?;
+            let value = value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .map(String::from)
+                .ok_or(format!("line {line_number}: values must be quoted"))?;
+
+            match key.trim() {
+                "session" => config.session = Some(value),
+                "base_url" => config.base_url = value,
+                "contact" => config.contact = Some(value),
+                other => return Err(format!("line {line_number}: unknown key '{other}'")),
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub fn user_agent(&self) -> String {
+        let agent = concat!("aoc-2023/", env!("CARGO_PKG_VERSION"));
+        match &self.contact {
+            Some(contact) => format!("{agent} (by {contact})"),
+            None => String::from(agent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            "# Puzzle site settings
+session = \"abc123\" # keep this secret
+base_url = \"http://127.0.0.1:9000\"
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                session: Some(String::from("abc123")),
+                base_url: String::from("http://127.0.0.1:9000"),
+                contact: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Config::parse("session abc").is_err());
+        assert!(Config::parse("session = abc").is_err());
+        assert!(Config::parse("token = \"abc\"").is_err());
+    }
+
+    #[test]
+    fn test_user_agent() {
+        let mut config = Config::default();
+        assert!(config.user_agent().starts_with("aoc-2023/"));
+
+        config.contact = Some(String::from("me@example.com"));
+        assert!(config.user_agent().ends_with(" (by me@example.com)"));
+    }
+}
@@ -1,109 +1,120 @@
-use std::collections::HashMap;
+//! [Day 1: Trebuchet?!](https://adventofcode.com/2023/day/1)
 
-fn parse_digit(item: &str, digit_map: &HashMap<&str, &str>) -> char {
-    if digit_map.contains_key(item) {
-        *digit_map.get(item).unwrap()
-    } else {
-        item
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::parse::Cursor;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// A digit found in a line, in figures or spelled out.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Digit {
+    value: u32,
+    spelled: bool,
+}
+
+/// Every digit in the line in order, including spelled out ones that overlap
+/// such as the `8` and `2` in `eightwo`.
+fn parse_line(line: Cursor) -> Result<Vec<Digit>, ParseError> {
+    let text = line.as_str();
+    let digits = text
+        .char_indices()
+        .filter_map(|(index, c)| match c.to_digit(10) {
+            Some(value) => Some(Digit {
+                value,
+                spelled: false,
+            }),
+            None => WORDS
+                .iter()
+                .position(|word| text[index..].starts_with(word))
+                .map(|position| Digit {
+                    value: position as u32 + 1,
+                    spelled: true,
+                }),
+        })
+        .collect::<Vec<_>>();
+
+    if digits.is_empty() {
+        return Err(line.error("expected a digit, in figures or spelled out"));
     }
-    .chars()
-    .next()
-    .unwrap()
+
+    Ok(digits)
 }
 
-fn parse_line(line: &str, to_find: &Vec<&str>, digit_map: &HashMap<&str, &str>) -> i32 {
-    let mut min_index = 999;
-    let mut max_index = 0;
-    let mut min_digit = '0';
-    let mut max_digit = '0';
-
-    for test in to_find {
-        match line.find(test) {
-            None => {}
-            Some(index) => {
-                if index < min_index {
-                    min_index = index;
-                    min_digit = parse_digit(test, digit_map);
-                }
-            }
-        };
-        match line.rfind(test) {
-            None => {}
-            Some(index) => {
-                if index >= max_index {
-                    max_index = index;
-                    max_digit = parse_digit(test, digit_map);
-                }
-            }
-        }
+/// Sums the calibration value on each line, counting spelled out digits in part 2.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Vec<Digit>>;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(sum_lines(input, false)?.into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(sum_lines(input, true)?.into())
     }
-    format!("{}{}", min_digit, max_digit).parse().unwrap()
 }
 
-pub fn exec(source: &str) -> (i32, i32) {
-    let lines = source.split('\n').collect::<Vec<_>>();
-
-    let digit_map = HashMap::from([
-        ("one", "1"),
-        ("two", "2"),
-        ("three", "3"),
-        ("four", "4"),
-        ("five", "5"),
-        ("six", "6"),
-        ("seven", "7"),
-        ("eight", "8"),
-        ("nine", "9"),
-    ]);
-
-    let values: Vec<_> = digit_map.values().cloned().collect();
-    let values_2 = [values.clone(), digit_map.keys().cloned().collect()].concat();
-
-    let (part_1, part_2) = lines
+fn sum_lines(lines: &[Vec<Digit>], spelled: bool) -> Result<u32, Error> {
+    lines
         .iter()
-        .map(|line| {
-            (
-                parse_line(line, &values, &digit_map),
-                parse_line(line, &values_2, &digit_map),
-            )
-        })
-        .unzip::<i32, i32, Vec<_>, Vec<_>>();
+        .map(|digits| {
+            let mut values = digits
+                .iter()
+                .filter(|digit| spelled || !digit.spelled)
+                .map(|digit| digit.value);
+            let first = values
+                .next()
+                .ok_or_else(|| Error::solve("expected a digit in figures on every line"))?;
 
-    (part_1.iter().sum(), part_2.iter().sum())
+            Ok(10 * first + values.next_back().unwrap_or(first))
+        })
+        .sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_input;
-
     use super::*;
 
-    #[test]
-    fn test_sample_data_1() {
-        let sample_data = read_input("1_sample_1");
-        assert_eq!(exec(&sample_data), (142, 142));
-    }
-
-    #[test]
-    fn test_sample_data_2() {
-        let sample_data = read_input("1_sample_2");
-        assert_eq!(exec(&sample_data), (209, 281));
-    }
-
     #[test]
     fn test_line_parse_1() {
         let sample_data = String::from("eightone7threenl7mtxbmkpkzqzljrdk");
-        assert_eq!(exec(&sample_data), (77, 87))
+        assert_eq!(Day1::exec(&sample_data), Ok((77.into(), 87.into())))
     }
 
     #[test]
     fn test_line_parse_2() {
         let sample_data = String::from("hzgrkrbmjmzhpfkfgg5");
-        assert_eq!(exec(&sample_data), (55, 55))
+        assert_eq!(Day1::exec(&sample_data), Ok((55.into(), 55.into())))
     }
 
     #[test]
     fn test_line_parse_3() {
         let sample_data = String::from("1");
-        assert_eq!(exec(&sample_data), (11, 11))
+        assert_eq!(Day1::exec(&sample_data), Ok((11.into(), 11.into())))
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let input = Day1::parse(&PuzzleInput::new("eightwo")).unwrap();
+        assert_eq!(Day1::part_2(&input), Ok(82.into()));
+    }
+
+    #[test]
+    fn test_line_without_digits() {
+        let error = Day1::exec("1\nabc\n2").unwrap_err();
+        assert!(matches!(error, Error::Parse(ParseError { line: 2, .. })));
+
+        let input = Day1::parse(&PuzzleInput::new("1\ntwo")).unwrap();
+        assert_eq!(Day1::part_2(&input), Ok(33.into()));
+        assert!(Day1::part_1(&input).is_err());
     }
 }
@@ -1,302 +1,192 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+//! [Day 10: Pipe Maze](https://adventofcode.com/2023/day/10)
+
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::geometry::{double_area, Direction, Point};
+use crate::utils::grid::Grid;
+use crate::utils::input::PuzzleInput;
+
+/// The tiles of the field, and where the animal starts.
+pub struct PipeMap {
+    /// The position of the starting tile `S`, if there is one.
+    pub start: Option<Point<usize>>,
+    /// Every tile, including ground.
+    pub pipes: Grid<Pipe>,
+}
 
-pub fn exec(source: &str) -> (usize, usize) {
-    let mut start: Option<Pipe> = None;
-    let mut pipes = vec![];
+/// Measures the furthest point along the loop from the start, then counts the
+/// tiles the loop encloses.
+pub struct Day10;
 
-    for (y, line) in source.split('\n').enumerate() {
-        let (new_pipes, new_start) = parse_line(&line, y as isize);
+impl Solution for Day10 {
+    type Input = PipeMap;
 
-        if Option::is_some(&new_start) {
-            start = new_start;
-        }
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        let pipes = Grid::parse(input.lines(), |c| Ok(Pipe::new(c)))?;
+        let start = pipes
+            .iter()
+            .find(|(_, pipe)| pipe.start)
+            .map(|(position, _)| position);
 
-        pipes.extend(new_pipes);
+        Ok(PipeMap { start, pipes })
     }
 
-    let map = pipes
-        .iter()
-        .map(|&pipe| ((pipe.x, pipe.y), pipe))
-        .collect::<HashMap<_, _>>();
-
-    let mut main_loop: Option<HashSet<Pipe>> = None;
-
-    let part_a = match start {
-        None => 0,
-        Some(s) => {
-            let result = get_max_pipe_distance(&s, &map);
-            main_loop = Some(result.1);
-            result.0
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(match input.start {
+            None => 0,
+            Some(start) => find_loop(start, &input.pipes)?.len() / 2,
         }
-    };
-
-    let main_loop = main_loop.unwrap();
-    let loop_map: HashMap<_, _> = main_loop
-        .iter()
-        .map(|pipe| ((pipe.x, pipe.y), pipe.clone()))
-        .collect();
-
-    let size_y = source.matches('\n').count() as isize;
-    let size_x = source.find('\n').unwrap() as isize;
+        .into())
+    }
 
-    let mut inside = 0;
+    /// By Pick's theorem, the area inside the loop is the number of tiles
+    /// enclosed plus half the tiles on the loop, less one.
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        let start = input
+            .start
+            .ok_or_else(|| Error::solve("the map has no 'S' start tile"))?;
+        let main_loop = find_loop(start, &input.pipes)?;
 
-    for y in 0..=size_y {
-        let mut north_on = false;
-        let mut south_on = false;
-        for x in 0..=size_x {
-            match loop_map.get(&(x, y)) {
-                None => {
-                    if north_on && south_on {
-                        inside += 1
-                    };
-                }
-                Some(pipe) => {
-                    if pipe.n {
-                        north_on = !north_on;
-                    };
-                    if pipe.s {
-                        south_on = !south_on;
-                    };
-                }
-            }
-        }
+        Ok(((double_area(&main_loop) - main_loop.len()) / 2 + 1).into())
     }
-
-    (part_a, inside)
 }
 
-fn get_max_pipe_distance(
-    start: &Pipe,
-    map: &HashMap<(isize, isize), Pipe>,
-) -> (usize, HashSet<Pipe>) {
-    let mut seen = HashSet::from([start.clone()]);
-
-    let mut stack = VecDeque::from([start.clone()]);
-
-    while let Some(current) = stack.pop_front() {
-        let neighbours = current.get_connections(&map);
-
-        neighbours.iter().for_each(|pipe| {
-            if !seen.contains(pipe) {
-                seen.insert(pipe.clone());
-                stack.push_front(pipe.clone());
-            }
-        });
+/// The tiles of the loop through `start`, in order around it.
+fn find_loop(start: Point<usize>, map: &Grid<Pipe>) -> Result<Vec<Point<usize>>, Error> {
+    let mut path = vec![start];
+    let mut current = start;
+    let mut arrived: Option<Direction> = None;
+
+    loop {
+        let (direction, next) = Direction::ALL
+            .into_iter()
+            .filter(|&direction| arrived.map(Direction::reverse) != Some(direction))
+            .find_map(|direction| {
+                map[current]
+                    .connection(current, direction, map)
+                    .map(|next| (direction, next))
+            })
+            .ok_or_else(|| Error::solve(format!("the pipe at {current} is a dead end")))?;
+
+        if next == start {
+            return Ok(path);
+        }
+        path.push(next);
+        current = next;
+        arrived = Some(direction);
     }
-
-    (seen.len() / 2, seen)
 }
 
+/// A tile of pipe, and which of its sides connect to its neighbours.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
-struct Pipe {
-    char: char,
-    start: bool,
-    x: isize,
-    y: isize,
-    n: bool,
-    e: bool,
-    s: bool,
-    w: bool,
+pub struct Pipe {
+    /// The tile as drawn in the input.
+    pub char: char,
+    /// Whether this is the starting tile, which may connect in any direction.
+    pub start: bool,
+    /// Connects north.
+    pub n: bool,
+    /// Connects east.
+    pub e: bool,
+    /// Connects south.
+    pub s: bool,
+    /// Connects west.
+    pub w: bool,
 }
 
 impl Pipe {
-    fn get_connections(&self, map: &HashMap<(isize, isize), Pipe>) -> HashSet<Pipe> {
-        let mut result = HashSet::with_capacity(2);
-
-        for (d, (x, y)) in CARDINALS {
-            let target = (self.x + x, self.y + y);
-
-            match map.get(&target) {
-                None => {}
-                Some(neighbour) => match d {
-                    Cardinal::N => {
-                        if neighbour.s && (self.start || self.n) {
-                            result.insert(neighbour.clone());
-                        };
-                    }
-                    Cardinal::S => {
-                        if neighbour.n && (self.start || self.s) {
-                            result.insert(neighbour.clone());
-                        };
-                    }
-                    Cardinal::E => {
-                        if neighbour.w && (self.start || self.e) {
-                            result.insert(neighbour.clone());
-                        };
-                    }
-                    Cardinal::W => {
-                        if neighbour.e && (self.start || self.w) {
-                            result.insert(neighbour.clone());
-                        };
-                    }
-                },
-            };
+    fn new(c: char) -> Pipe {
+        Pipe {
+            char: c,
+            start: c == 'S',
+            n: NORTH.contains(&c),
+            e: EAST.contains(&c),
+            s: SOUTH.contains(&c),
+            w: WEST.contains(&c),
         }
+    }
 
-        result
+    /// Whether this pipe has an opening facing `direction`.
+    fn opens(&self, direction: Direction) -> bool {
+        self.start
+            || match direction {
+                Direction::North => self.n,
+                Direction::East => self.e,
+                Direction::South => self.s,
+                Direction::West => self.w,
+            }
     }
-}
 
-enum Cardinal {
-    N,
-    S,
-    E,
-    W,
+    /// The neighbour of this pipe, at `position`, that it joins up with in
+    /// `direction`.
+    fn connection(
+        &self,
+        position: Point<usize>,
+        direction: Direction,
+        map: &Grid<Pipe>,
+    ) -> Option<Point<usize>> {
+        map.step(position, direction)
+            .filter(|&next| self.opens(direction) && map[next].opens(direction.reverse()))
+    }
 }
 
-const CARDINALS: [(Cardinal, (isize, isize)); 4] = [
-    (Cardinal::N, (0, -1)),
-    (Cardinal::S, (0, 1)),
-    (Cardinal::W, (-1, 0)),
-    (Cardinal::E, (1, 0)),
-];
-
 const NORTH: [char; 3] = ['|', 'L', 'J'];
 const EAST: [char; 3] = ['-', 'F', 'L'];
 const SOUTH: [char; 3] = ['|', '7', 'F'];
 const WEST: [char; 3] = ['-', 'J', '7'];
 
-fn parse_line(line: &str, y: isize) -> (Vec<Pipe>, Option<Pipe>) {
-    let mut start = None;
-
-    let result = line
-        .chars()
-        .enumerate()
-        .map(|(x, c)| {
-            let new = Pipe {
-                char: c,
-                start: c == 'S',
-                x: x as isize,
-                y,
-                n: NORTH.contains(&c),
-                e: EAST.contains(&c),
-                s: SOUTH.contains(&c),
-                w: WEST.contains(&c),
-            };
-            if new.start {
-                start = Some(new)
-            }
-            new
-        })
-        .collect();
-
-    (result, start)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_line() {
+    fn test_new_pipe() {
         assert_eq!(
-            parse_line("F", 0),
-            (
-                vec![Pipe {
-                    char: 'F',
-                    start: false,
-                    x: 0,
-                    y: 0,
-                    n: false,
-                    e: true,
-                    s: true,
-                    w: false,
-                }],
-                None
-            )
+            Pipe::new('F'),
+            Pipe {
+                char: 'F',
+                start: false,
+                n: false,
+                e: true,
+                s: true,
+                w: false,
+            }
         )
     }
 
     #[test]
-    fn test_map_connections() {
-        let pipe_1 = Pipe {
-            char: 'x',
-            start: false,
-            x: 0,
-            y: 0,
-            n: false,
-            s: false,
-            e: true,
-            w: true,
-        };
-        let pipe_2 = Pipe {
-            char: 'x',
-            start: false,
-            x: 1,
-            y: 0,
-            n: false,
-            s: false,
-            e: true,
-            w: true,
-        };
-        let pipe_3 = Pipe {
-            char: 'x',
-            start: false,
-            x: 2,
-            y: 0,
-            n: false,
-            s: false,
-            e: true,
-            w: true,
-        };
-        let pipe_4 = Pipe {
-            char: 'x',
-            start: false,
-            x: 1,
-            y: 1,
-            n: true,
-            s: false,
-            e: true,
-            w: false,
-        };
-        let map = HashMap::from([
-            ((0, 0), pipe_1),
-            ((1, 0), pipe_2),
-            ((2, 0), pipe_3),
-            ((1, 1), pipe_4),
-        ]);
-
+    fn test_missing_start() {
         assert_eq!(
-            pipe_2.get_connections(&map),
-            HashSet::from([pipe_1, pipe_3])
-        )
-    }
-
-    #[test]
-    fn test_sample_1() {
-        let sample = "-L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF";
-
-        assert_eq!(exec(&sample).0, 4);
+            Day10::exec("F7\nLJ").unwrap_err(),
+            Error::solve("the map has no 'S' start tile")
+        );
     }
 
     #[test]
-    fn test_sample_2() {
-        let sample = "7-F7-
-.FJ|7
-SJLL7
-|F--J
-LJ.LJ";
+    fn test_map_connections() {
+        let map = Grid::parse("---\n.L.", |c| Ok(Pipe::new(c))).unwrap();
+        let connections = |x, y| {
+            Direction::ALL
+                .into_iter()
+                .filter_map(|direction| {
+                    map[Point::new(x, y)].connection(Point::new(x, y), direction, &map)
+                })
+                .collect::<Vec<_>>()
+        };
 
-        assert_eq!(exec(&sample).0, 8);
+        assert_eq!(connections(1, 0), vec![Point::new(2, 0), Point::new(0, 0)]);
+        assert_eq!(connections(1, 1), vec![]);
     }
 
     #[test]
-    fn test_sample_3() {
-        let sample = "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........";
+    fn test_find_loop() {
+        let map = Grid::parse(".....\n.S-7.\n.|.|.\n.L-J.\n.....", |c| Ok(Pipe::new(c))).unwrap();
 
-        assert_eq!(exec(&sample).1, 4);
+        assert_eq!(find_loop(Point::new(1, 1), &map).unwrap().len(), 8);
+        assert_eq!(
+            Day10::exec(".....\n.S-7.\n.|.|.\n.L-J.\n....."),
+            Ok((4.into(), 1.into()))
+        );
+        assert!(Day10::exec("S-7").is_err());
     }
 }
@@ -1,158 +1,114 @@
-use std::collections::HashMap;
+//! [Day 11: Cosmic Expansion](https://adventofcode.com/2023/day/11)
 
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::geometry::Point;
+use crate::utils::grid::Grid;
+use crate::utils::input::PuzzleInput;
+
+/// A tile of the image.
 #[derive(PartialEq, Debug, Clone)]
-enum Space {
+pub enum Space {
+    /// `#`
     Galaxy,
+    /// `.`
     Nothing,
 }
 
-pub fn exec(source: &str) -> (usize, usize) {
-    let data = source
-        .split('\n')
-        .map(|line| parse_line(&line))
-        .collect::<Vec<_>>();
+/// Sums the distances between each pair of galaxies once empty rows and
+/// columns have expanded.
+pub struct Day11;
 
-    (calculate(&data, 1), calculate(&data, 999999))
-}
+impl Solution for Day11 {
+    type Input = Grid<Space>;
 
-fn calculate(map: &Vec<Vec<Space>>, dilate_by: usize) -> usize {
-    let data = map.clone();
-    let mut galaxies = HashMap::new();
-
-    for (y, line) in data.iter().enumerate() {
-        for (x, item) in line.iter().enumerate() {
-            match item {
-                Space::Galaxy => {
-                    galaxies.insert((x, y), (x, y));
-                }
-                _ => {}
-            }
-        }
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        Grid::parse(input.lines(), parse_space)
     }
 
-    insert_blanks(&data, &mut galaxies, dilate_by, false);
-    let data = transpose(&data);
-    insert_blanks(&data, &mut galaxies, dilate_by, true);
-
-    let mut result = 0;
-
-    let galaxies = galaxies.values().collect::<Vec<_>>();
-
-    for (index, &&left) in galaxies.iter().enumerate() {
-        for &&right in galaxies.iter().skip(index + 1) {
-            result += distance(left, right);
-        }
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(calculate(input, 1).into())
     }
 
-    result
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(calculate(input, 999999).into())
+    }
 }
 
-fn distance(a: (usize, usize), b: (usize, usize)) -> usize {
-    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
-}
+fn calculate(image: &Grid<Space>, dilate_by: usize) -> usize {
+    let galaxies = expand(image, dilate_by);
+    let mut result = 0;
 
-fn insert_blanks(
-    space: &Vec<Vec<Space>>,
-    galaxies: &mut HashMap<(usize, usize), (usize, usize)>,
-    dilate_by: usize,
-    tranposed: bool,
-) {
-    let mut blanks = vec![];
-
-    for (index, line) in space.iter().enumerate().rev() {
-        if line.iter().all(|p| p == &Space::Nothing) {
-            blanks.push(index);
+    for (index, &left) in galaxies.iter().enumerate() {
+        for &right in galaxies.iter().skip(index + 1) {
+            result += left.manhattan(right);
         }
     }
 
-    let mut dilate_amount = 0;
-    for (y, line) in space.iter().enumerate() {
-        if blanks.contains(&y) {
-            dilate_amount += 1;
-        }
-        for (x, _) in line.iter().enumerate() {
-            let key = if tranposed { (y, x) } else { (x, y) };
-
-            match galaxies.get_mut(&key) {
-                None => {}
-                Some(entry) => {
-                    if tranposed {
-                        entry.0 += dilate_by * dilate_amount;
-                    } else {
-                        entry.1 += dilate_by * dilate_amount;
-                    }
-                }
-            }
-        }
-    }
+    result
 }
 
-fn transpose(matrix: &Vec<Vec<Space>>) -> Vec<Vec<Space>> {
-    let mut transposed = vec![Vec::new(); matrix.len()];
-
-    for row in matrix.iter() {
-        for (index, item) in row.iter().enumerate() {
-            transposed[index].push(item.clone());
-        }
-    }
+/// Where each galaxy ends up once every empty row and column has grown by
+/// `dilate_by` more.
+fn expand(image: &Grid<Space>, dilate_by: usize) -> Vec<Point<usize>> {
+    let empty_columns = image
+        .columns()
+        .map(|mut column| column.all(|p| p == &Space::Nothing))
+        .collect::<Vec<_>>();
+    let empty_rows = image
+        .rows()
+        .map(|row| row.iter().all(|p| p == &Space::Nothing))
+        .collect::<Vec<_>>();
 
-    transposed
-}
+    let before = |empty: &[bool], index: usize| empty[..index].iter().filter(|&&e| e).count();
 
-fn parse_line(line: &str) -> Vec<Space> {
-    line.chars()
-        .map(|c| match c {
-            '.' => Space::Nothing,
-            '#' => Space::Galaxy,
-            _ => panic!("Not space"),
+    image
+        .iter()
+        .filter(|(_, item)| *item == &Space::Galaxy)
+        .map(|(Point { x, y }, _)| {
+            Point::new(
+                x + dilate_by * before(&empty_columns, x),
+                y + dilate_by * before(&empty_rows, y),
+            )
         })
         .collect()
 }
 
+fn parse_space(c: char) -> Result<Space, &'static str> {
+    match c {
+        '.' => Ok(Space::Nothing),
+        '#' => Ok(Space::Galaxy),
+        _ => Err("expected '.' or '#'"),
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::utils::read_input;
-
     use super::*;
 
     #[test]
-    fn test_parse_line() {
+    fn test_parse() {
+        let image = Day11::parse(&PuzzleInput::new(".#.\n...")).unwrap();
         assert_eq!(
-            parse_line(".#."),
-            vec![Space::Nothing, Space::Galaxy, Space::Nothing]
+            image.row(0),
+            Some(&[Space::Nothing, Space::Galaxy, Space::Nothing][..])
         );
     }
 
     #[test]
-    fn test_insert_blanks() {
-        let sample = vec![
-            vec![Space::Nothing, Space::Nothing],
-            vec![Space::Nothing, Space::Nothing],
-            vec![Space::Nothing, Space::Galaxy],
-        ];
-        let mut galaxies = HashMap::from([((1, 2), (1, 2))]);
-        insert_blanks(&sample, &mut galaxies, 1, false);
-        let x = galaxies.get(&(1, 2)).unwrap();
-        assert_eq!(x, &(1, 4));
+    fn test_parse_error() {
+        let error = Day11::parse(&PuzzleInput::new("..\n.x")).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
     }
 
     #[test]
-    fn test_rotate() {
-        let sample = vec![
-            vec![Space::Nothing, Space::Nothing],
-            vec![Space::Galaxy, Space::Galaxy],
-        ];
-        let expected = vec![
-            vec![Space::Nothing, Space::Galaxy],
-            vec![Space::Nothing, Space::Galaxy],
-        ];
-
-        assert_eq!(transpose(&sample), expected);
+    fn test_expand() {
+        let image = Day11::parse(&PuzzleInput::new("..\n..\n.#")).unwrap();
+        assert_eq!(expand(&image, 1), vec![Point::new(2, 4)]);
     }
 
     #[test]
-    fn test_sample() {
-        let sample = read_input("11_sample_1");
-        assert_eq!(exec(&sample), (374, 82000210));
+    fn test_wider_than_tall() {
+        assert_eq!(Day11::exec("#...\n...#"), Ok((6.into(), 2000002.into())));
     }
 }
@@ -1,25 +1,53 @@
+//! [Day 12: Hot Springs](https://adventofcode.com/2023/day/12)
+
 use cached::proc_macro::cached;
+use cached::Cached;
 use itertools::Itertools;
 
-pub fn exec(source: &str) -> (usize, usize) {
-    let part_a = source
-        .split("\n")
-        .map(|line| {
-            let line = parse_line(line);
-            solve(String::from(line.0), line.1, false)
-        })
-        .sum();
-
-    let part_b = source
-        .split("\n")
-        .map(|line| {
-            let line = parse_line(line);
-            let left = vec![line.0].repeat(5).join("?");
-            solve(left, line.1.repeat(5), false)
-        })
-        .sum();
-
-    (part_a, part_b)
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::parse::{split_once_or_err, Cursor};
+
+/// Counts the spring arrangements that fit each record, unfolded five times in
+/// part 2.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<(String, Vec<usize>)>;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|line| {
+                let (left, right) = parse_line(line)?;
+                Ok((String::from(left), right))
+            })
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(input
+            .iter()
+            .map(|(line, arrangement)| solve(line.clone(), arrangement.clone(), false))
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(input
+            .iter()
+            .map(|(line, arrangement)| {
+                let left = [line.as_str(); 5].join("?");
+                solve(left, arrangement.repeat(5), false)
+            })
+            .sum::<usize>()
+            .into())
+    }
+
+    fn reset() {
+        SOLVE.lock().unwrap().cache_clear();
+    }
 }
 
 #[cached]
@@ -29,60 +57,67 @@ fn solve(line: String, arrangement: Vec<usize>, in_match: bool) -> usize {
         return if matches == 0 { 1 } else { 0 };
     }
 
-    let remaining = *arrangement.get(0).unwrap_or(&0);
+    let remaining = *arrangement.first().unwrap_or(&0);
     let (_, new_line) = line.split_at(1);
     let substr = String::from(new_line);
-    let mut new_arrangement = arrangement.iter().skip(1).map(|i| *i).collect_vec();
+    let mut new_arrangement = arrangement.iter().skip(1).copied().collect_vec();
 
     match line.chars().next() {
-        None => {}
+        None => 0,
         Some('#') => {
             if remaining == 0 {
                 return 0;
             }
 
             new_arrangement.insert(0, remaining - 1);
-            return solve(substr, new_arrangement, true);
+            solve(substr, new_arrangement, true)
         }
         Some('.') => {
             if in_match && remaining != 0 {
-                return 0;
+                0
             } else if remaining == 0 {
-                return solve(substr, new_arrangement, false);
+                solve(substr, new_arrangement, false)
             } else {
-                return solve(substr, arrangement, false);
+                solve(substr, arrangement, false)
             }
         }
         Some('?') => {
             let a = format!("#{}", substr);
             let b = format!(".{}", substr);
-            return solve(a, arrangement.clone(), in_match) + solve(b, arrangement, in_match);
+            solve(a, arrangement.clone(), in_match) + solve(b, arrangement, in_match)
         }
-        _ => panic!("boo"),
+        _ => unreachable!("parse_line only lets through '#', '.' and '?'"),
     }
-
-    0
 }
 
-fn parse_line(line: &str) -> (&str, Vec<usize>) {
-    let (left, right) = line.split_once(' ').unwrap();
-    (left, right.split(',').map(|c| c.parse().unwrap()).collect())
+fn parse_line<'a>(line: impl Into<Cursor<'a>>) -> Result<(&'a str, Vec<usize>), ParseError> {
+    let (springs, groups) = split_once_or_err(line, " ", "expected '<springs> <groups>'")?;
+
+    if let Some((_, at)) = springs.chars().find(|(c, _)| !matches!(c, '#' | '.' | '?')) {
+        return Err(at.error("expected '#', '.' or '?'"));
+    }
+
+    let groups = groups
+        .split(",")
+        .map(|group| group.number())
+        .collect::<Result<_, _>>()?;
+
+    Ok((springs.as_str(), groups))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_input;
-
     use super::*;
 
     #[test]
-    fn test_sample_data_1() {
-        let sample_data = read_input("12_sample_1");
-        assert_eq!(exec(&sample_data).0, 21);
+    fn test_parse_line() {
+        assert_eq!(parse_line("#.#.### 1,1,3"), Ok(("#.#.###", vec![1, 1, 3])));
     }
 
     #[test]
-    fn test_parse_line() {
-        assert_eq!(parse_line("#.#.### 1,1,3"), ("#.#.###", vec![1, 1, 3]));
+    fn test_parse_line_errors() {
+        assert_eq!(parse_line("#.#.###").unwrap_err().column, 1);
+        assert_eq!(parse_line("#.x.### 1").unwrap_err().column, 3);
+        assert_eq!(parse_line("#.#.### 1,,3").unwrap_err().column, 11);
     }
 }
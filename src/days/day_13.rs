@@ -1,63 +1,108 @@
+//! [Day 13: Point of Incidence](https://adventofcode.com/2023/day/13)
+
 use std::collections::HashMap;
 
 use itertools::min;
 
-#[derive(Ord, Eq, PartialEq, PartialOrd, Clone)]
-enum Type {
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::grid::Grid;
+use crate::utils::input::PuzzleInput;
+
+/// A tile of a pattern.
+#[derive(Ord, Eq, PartialEq, PartialOrd, Clone, Debug)]
+pub enum Type {
+    /// `.`
     Ash,
+    /// `#`
     Rock,
 }
 
-pub fn exec(source: &str) -> (usize, usize) {
-    let grids = parse_grids(source);
+/// Summarises the line of reflection in each pattern, then the one left after
+/// fixing its smudge.
+pub struct Day13;
 
-    let mut part_a = 0;
-    let mut part_b = 0;
+impl Solution for Day13 {
+    type Input = Vec<Grid<Type>>;
 
-    grids.iter().for_each(|grid| {
-        let (l, r) = find_rotating_symmetry_line(grid);
-        part_a += l.unwrap();
-        part_b += r.unwrap();
-    });
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        parse_grids(input)
+    }
 
-    (part_a, part_b)
-}
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(summarise(input, clean_line, "clean")?.into())
+    }
 
-fn parse_grids(source: &str) -> Vec<Vec<Vec<Type>>> {
-    source.split("\n\n").map(|grid| parse_grid(grid)).collect()
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(summarise(input, smudged_line, "smudged")?.into())
+    }
 }
 
-fn parse_grid(grid: &str) -> Vec<Vec<Type>> {
-    grid.split('\n').map(|line| parse_line(line)).collect()
+/// Add up each grid's summary from `line`, failing on the first grid that
+/// doesn't have one.
+fn summarise(
+    input: &[Grid<Type>],
+    line: fn(&Grid<Type>) -> Option<usize>,
+    kind: &str,
+) -> Result<usize, Error> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(index, grid)| {
+            line(grid).ok_or_else(|| {
+                Error::solve(format!(
+                    "grid {} doesn't have exactly one {kind} reflection",
+                    index + 1
+                ))
+            })
+        })
+        .sum()
 }
 
-fn parse_line(line: &str) -> Vec<Type> {
-    line.chars()
-        .map(|c| match c {
-            '#' => Type::Rock,
-            '.' => Type::Ash,
-            _ => panic!("Oopsie Whoopsie"),
-        })
+fn parse_grids(input: &PuzzleInput) -> Result<Vec<Grid<Type>>, ParseError> {
+    input
+        .blocks()
+        .iter()
+        .map(|block| Grid::parse(block.lines(), parse_type))
         .collect()
 }
 
-fn find_rotating_symmetry_line(grid: &Vec<Vec<Type>>) -> (Option<usize>, Option<usize>) {
-    match (
-        find_symmetry_line(&grid),
-        find_symmetry_line(&transpose(&grid)),
-    ) {
-        ((Some(a), Some(b)), (None, None)) => (Some(a), Some(b)),
-        ((Some(a), None), (None, Some(b))) => (Some(a), Some(b * 100)),
-        ((None, Some(b)), (Some(a), None)) => (Some(a * 100), Some(b)),
-        ((None, None), (Some(a), Some(b))) => (Some(a * 100), Some(b * 100)),
-        _ => panic!("Shouldn't be possible"),
+fn parse_type(c: char) -> Result<Type, &'static str> {
+    match c {
+        '#' => Ok(Type::Rock),
+        '.' => Ok(Type::Ash),
+        _ => Err("expected '#' or '.'"),
+    }
+}
+
+/// The summary of the grid's perfect reflection.
+fn clean_line(grid: &Grid<Type>) -> Option<usize> {
+    reflection_line(grid, 0)
+}
+
+/// The summary of the reflection the grid has once its smudge is fixed.
+fn smudged_line(grid: &Grid<Type>) -> Option<usize> {
+    reflection_line(grid, 1)
+}
+
+/// The summary of the only line of reflection spoilt by exactly `differences`
+/// tiles: its column, or 100 times its row. None unless there's exactly one.
+fn reflection_line(grid: &Grid<Type>, differences: usize) -> Option<usize> {
+    let columns = symmetry_lines(grid, differences);
+    let rows = symmetry_lines(&grid.transpose(), differences);
+
+    match (columns.as_slice(), rows.as_slice()) {
+        (&[column], []) => Some(column),
+        ([], &[row]) => Some(row * 100),
+        _ => None,
     }
 }
 
-fn find_symmetry_line(grid: &Vec<Vec<Type>>) -> (Option<usize>, Option<usize>) {
+/// Every vertical line of reflection spoilt by exactly `differences` tiles.
+fn symmetry_lines(grid: &Grid<Type>, differences: usize) -> Vec<usize> {
     let mut result: HashMap<usize, usize> = HashMap::new();
 
-    for line in grid {
+    for line in grid.rows() {
         let set = find_symmetry_lines(line);
 
         for (i, diffs) in set {
@@ -65,21 +110,16 @@ fn find_symmetry_line(grid: &Vec<Vec<Type>>) -> (Option<usize>, Option<usize>) {
         }
     }
 
-    let mut zero = None;
-    let mut one = None;
-
-    for (i, diff) in result {
-        if diff == 0 {
-            zero = Some(i)
-        } else if diff == 1 {
-            one = Some(i)
-        }
-    }
-
-    (zero, one)
+    let mut lines = result
+        .into_iter()
+        .filter(|&(_, diffs)| diffs == differences)
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    lines.sort();
+    lines
 }
 
-fn find_symmetry_lines(line: &Vec<Type>) -> HashMap<usize, usize> {
+fn find_symmetry_lines(line: &[Type]) -> HashMap<usize, usize> {
     let mut result = HashMap::new();
 
     for i in 0..line.len() {
@@ -109,23 +149,23 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_sample_data_1() {
-        let sample_data = read_input("13_sample_1");
-        assert_eq!(exec(&sample_data), (405, 400));
+    fn test_crlf_sample() {
+        let sample_data = read_input("13_sample_1").replace('\n', " \r\n") + "\n";
+        assert_eq!(Day13::exec(&sample_data), Ok((405.into(), 400.into())));
     }
 
     #[test]
     fn test_symmetry_finder() {
-        let line = parse_line("#....#");
+        let grid = Grid::parse("#....#", parse_type).unwrap();
 
-        let result = find_symmetry_lines(&line);
+        let result = find_symmetry_lines(grid.row(0).unwrap());
 
         assert!(*result.get(&3).unwrap() == 0);
     }
 
     #[test]
-    fn test_find_symmetry_line() {
-        let sample = parse_grid(
+    fn test_symmetry_lines() {
+        let sample = Grid::parse(
             "#.##..##.
 ..#.##.#.
 ##......#
@@ -133,14 +173,17 @@ mod tests {
 ..#.##.#.
 ..##..##.
 #.#.##.#.",
-        );
+            parse_type,
+        )
+        .unwrap();
 
-        assert_eq!(find_symmetry_line(&sample), (Some(5), None))
+        assert_eq!(symmetry_lines(&sample, 0), vec![5]);
+        assert_eq!(symmetry_lines(&sample, 1), vec![]);
     }
 
     #[test]
     fn test_find_transposed_symmetry_line() {
-        let sample = parse_grid(
+        let sample = Grid::parse(
             "#...##..#
 #....#..#
 ..##..###
@@ -148,20 +191,32 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#",
-        );
+            parse_type,
+        )
+        .unwrap();
 
-        assert_eq!(find_rotating_symmetry_line(&sample), (Some(400), Some(100)))
+        assert_eq!(clean_line(&sample), Some(400));
+        assert_eq!(smudged_line(&sample), Some(100));
     }
-}
 
-fn transpose<T: Clone>(matrix: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    let mut transposed = vec![Vec::new(); matrix.iter().next().unwrap().len()];
+    #[test]
+    fn test_parse_grids_error() {
+        let error = parse_grids(&PuzzleInput::new("#.\n.#\n\n##\n.o")).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 2));
+    }
 
-    for row in matrix.iter() {
-        for (index, item) in row.iter().enumerate() {
-            transposed[index].push(item.clone());
-        }
+    #[test]
+    fn test_no_reflection() {
+        let sample = Grid::parse("#.\n..", parse_type).unwrap();
+        assert_eq!(clean_line(&sample), None);
+        assert!(Day13::part_1(&vec![sample]).is_err());
     }
 
-    transposed
+    #[test]
+    fn test_clean_reflection_without_smudge() {
+        let input = vec![Grid::parse("#.#\n#.#", parse_type).unwrap()];
+
+        assert_eq!(Day13::part_1(&input), Ok(100.into()));
+        assert!(Day13::part_2(&input).is_err());
+    }
 }
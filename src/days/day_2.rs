@@ -1,14 +1,28 @@
+//! [Day 2: Cube Conundrum](https://adventofcode.com/2023/day/2)
+
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::parse::{key_value, split_once_or_err, Cursor};
+
+/// One line of the record: the cubes shown in each round of a game.
 #[derive(PartialEq, Debug)]
-struct Game {
-    id: i32,
-    matches: Vec<Match>,
+pub struct Game {
+    /// The game's number.
+    pub id: i32,
+    /// The handful of cubes revealed in each round.
+    pub matches: Vec<Match>,
 }
 
+/// How many cubes of each colour were revealed in one round.
 #[derive(PartialEq, Debug)]
-struct Match {
-    red: i32,
-    green: i32,
-    blue: i32,
+pub struct Match {
+    /// Number of red cubes.
+    pub red: i32,
+    /// Number of green cubes.
+    pub green: i32,
+    /// Number of blue cubes.
+    pub blue: i32,
 }
 
 impl Match {
@@ -31,78 +45,100 @@ impl Game {
 
     fn is_valid(&self) -> bool {
         let stones = self.max_stones();
-        return stones.red <= 12 && stones.green <= 13 && stones.blue <= 14;
+        stones.red <= 12 && stones.green <= 13 && stones.blue <= 14
     }
 }
 
-fn parse_line(line: &str) -> Game {
-    let (left, right) = line.split_once(": ").unwrap();
-    let id = left[5..].parse().unwrap();
+fn parse_line<'a>(line: impl Into<Cursor<'a>>) -> Result<Game, ParseError> {
+    let (game, pulls) = key_value(line)?;
+    let id = game.strip_prefix("Game ")?.number()?;
     let mut matches = vec![];
 
-    for result in right.split("; ") {
+    for result in pulls.split("; ") {
         let (mut red, mut green, mut blue) = (0, 0, 0);
 
         for pull in result.split(", ") {
-            let (num, colour) = pull.split_once(" ").unwrap();
-            let num = num.parse::<i32>().unwrap();
-            match colour {
+            let (num, colour) = split_once_or_err(pull, " ", "expected '<count> <colour>'")?;
+            let num = num.number::<i32>()?;
+            match colour.as_str() {
                 "red" => red += num,
                 "green" => green += num,
                 "blue" => blue += num,
-                _ => panic!("Whoops"),
+                _ => return Err(colour.error("unknown colour")),
             }
         }
         matches.push(Match { red, green, blue })
     }
 
-    Game { id, matches }
+    Ok(Game { id, matches })
 }
 
-pub fn exec(source: &str) -> (i32, i32) {
-    let games = source
-        .split("\n")
-        .map(|line| parse_line(line))
-        .collect::<Vec<_>>();
-
-    let (part_1, part_2) = games
-        .iter()
-        .map(|game| {
-            (
-                if game.is_valid() { game.id } else { 0 },
-                game.max_stones().power(),
-            )
-        })
-        .unzip::<i32, i32, Vec<_>, Vec<_>>();
-
-    (part_1.iter().sum(), part_2.iter().sum())
+/// Checks which games are possible with 12 red, 13 green and 14 blue cubes, then
+/// sums the power of the fewest cubes each game needs.
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Game>;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(input
+            .iter()
+            .filter(|game| game.is_valid())
+            .map(|game| game.id)
+            .sum::<i32>()
+            .into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(input
+            .iter()
+            .map(|game| game.max_stones().power())
+            .sum::<i32>()
+            .into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::panic::catch_unwind;
-
     use crate::utils::read_input;
 
     use super::*;
 
     #[test]
-    fn test_part_1_with_sample() {
-        let sample_data = read_input("2_sample_1");
-        assert_eq!(exec(&sample_data), (8, 2286))
+    fn test_crlf_sample() {
+        let sample_data = read_input("2_sample_1").replace('\n', "\r\n") + "\r\n";
+        assert_eq!(Day2::exec(&sample_data), Ok((8.into(), 2286.into())))
     }
 
     #[test]
     fn test_bad_colour() {
-        let result = catch_unwind(|| parse_line("Game 1: 3 purple"));
-        assert!(result.is_err());
+        let error = parse_line("Game 1: 3 purple").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (11, "purple"));
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        assert_eq!(parse_line("Game 1 3 blue").unwrap_err().column, 1);
+        assert_eq!(parse_line("Gam 1: 3 blue").unwrap_err().column, 1);
+        assert_eq!(parse_line("Game 1: 3blue").unwrap_err().column, 9);
+        assert_eq!(parse_line("Game 1: x blue").unwrap_err().column, 9);
+        assert_eq!(
+            Day2::parse(&PuzzleInput::new("Game 1: 3 blue\nGame 2: 4 red, 1 teal"))
+                .unwrap_err()
+                .line,
+            2
+        );
     }
 
     #[test]
     fn test_parse_line() {
         assert_eq!(
             parse_line("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
-            Game {
+            Ok(Game {
                 id: 1,
                 matches: vec![
                     Match {
@@ -121,7 +157,7 @@ mod tests {
                         blue: 0,
                     },
                 ],
-            }
+            })
         );
     }
 }
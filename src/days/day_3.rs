@@ -1,39 +1,57 @@
+//! [Day 3: Gear Ratios](https://adventofcode.com/2023/day/3)
+
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::geometry::{BoundingBox, Point};
+use crate::utils::grid::Grid;
+use crate::utils::input::PuzzleInput;
+
+/// A symbol in the schematic.
 #[derive(PartialEq, Debug, Clone)]
-struct Point(i32, i32, bool);
+pub struct Symbol {
+    /// Where it is.
+    pub position: Point<i32>,
+    /// Whether it's a gear symbol `*`.
+    pub gear: bool,
+}
 
+/// A number in the schematic.
 #[derive(PartialEq, Debug, Clone)]
-struct Part {
-    number: String,
-    coord: Point,
+pub struct Part {
+    /// The digits as written.
+    pub number: String,
+    /// Where its first digit is.
+    pub coord: Point<i32>,
 }
 
 impl Part {
     fn value(&self) -> i32 {
         self.number.parse().unwrap()
     }
-    fn in_range(&self, point: &Point) -> bool {
-        let min_x = self.coord.0 - 1;
-        let max_x = self.coord.0 + self.number.chars().count() as i32;
-        let min_y = self.coord.1 - 1;
-        let max_y = self.coord.1 + 1;
-
-        point.0 >= min_x && point.0 <= max_x && point.1 >= min_y && point.1 <= max_y
+    fn in_range(&self, position: Point<i32>) -> bool {
+        let length = self.number.chars().count() as i32;
+        let surroundings = BoundingBox::new(
+            self.coord - Point::new(1, 1),
+            self.coord + Point::new(length, 1),
+        );
+
+        surroundings.contains(position)
     }
 }
 
-fn calc_part_2(points: &Vec<Point>, parts: &Vec<Part>) -> i32 {
+fn calc_part_2(symbols: &[Symbol], parts: &[Part]) -> i32 {
     let mut result = 0;
 
-    for point in points {
-        if !point.2 {
+    for symbol in symbols {
+        if !symbol.gear {
             continue;
         }
 
         let filtered_parts = parts
             .iter()
-            .filter(|part| part.in_range(point))
+            .filter(|part| part.in_range(symbol.position))
             .collect::<Vec<_>>();
-        let parts_in_range = filtered_parts.iter().count();
+        let parts_in_range = filtered_parts.len();
 
         if parts_in_range == 2 {
             result += filtered_parts
@@ -46,66 +64,107 @@ fn calc_part_2(points: &Vec<Point>, parts: &Vec<Part>) -> i32 {
     result
 }
 
-pub fn exec(source: &str) -> (i32, i32) {
-    let (points, parts) = get_parts_and_points(source);
+/// Sums the part numbers next to a symbol, then the gear ratios of the `*`
+/// symbols next to exactly two of them.
+pub struct Day3;
 
-    let part_1 = parts.iter().map(|part| {
-        if points.iter().any(|point| part.in_range(point)) {
-            part.value()
-        } else {
-            0
+impl Solution for Day3 {
+    type Input = (Vec<Symbol>, Vec<Part>);
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        let schematic = Grid::parse(input.lines(), Ok)?;
+        let (symbols, parts) = get_parts_and_symbols(&schematic);
+
+        if let Some(part) = parts
+            .iter()
+            .find(|part| part.number.parse::<i32>().is_err())
+        {
+            return Err(number_error(input, part));
         }
-    });
 
-    (part_1.sum(), calc_part_2(&points, &parts))
+        Ok((symbols, parts))
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        let (symbols, parts) = input;
+
+        Ok(parts
+            .iter()
+            .filter(|part| symbols.iter().any(|symbol| part.in_range(symbol.position)))
+            .map(|part| part.value())
+            .sum::<i32>()
+            .into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        let (symbols, parts) = input;
+        Ok(calc_part_2(symbols, parts).into())
+    }
 }
 
-fn parse_line(line: &str, line_index: i32) -> (Vec<Point>, Vec<Part>) {
+/// Reports a part number that's too long to count, where it is written.
+fn number_error(input: &PuzzleInput, part: &Part) -> ParseError {
+    let (x, y) = (part.coord.x as usize, part.coord.y as usize);
+    let message = "expected a part number that fits in 32 bits";
+
+    match input.lines().nth(y) {
+        Some(line) => line
+            .skip_chars(x)
+            .take_while(|c| c.is_ascii_digit())
+            .error(message),
+        None => ParseError::at("", "", message),
+    }
+}
+
+fn parse_row(row: &[char], line_index: i32) -> (Vec<Symbol>, Vec<Part>) {
     let mut parts = Vec::with_capacity(20);
-    let mut points = Vec::with_capacity(20);
+    let mut symbols = Vec::with_capacity(20);
     let mut current_part = Part {
         number: String::with_capacity(3),
-        coord: Point(-1, line_index, false),
+        coord: Point::new(-1, line_index),
     };
 
-    for (index, char) in line.chars().enumerate() {
+    for (index, &char) in row.iter().enumerate() {
         if char.is_ascii_digit() {
             current_part.number.push(char);
-            if current_part.coord.0 == -1 {
-                current_part.coord = Point(index as i32, line_index, false);
+            if current_part.coord.x == -1 {
+                current_part.coord = Point::new(index as i32, line_index);
             }
         } else {
             if char != '.' {
-                points.push(Point(index as i32, line_index, char == '*'));
+                symbols.push(Symbol {
+                    position: Point::new(index as i32, line_index),
+                    gear: char == '*',
+                });
             }
-            if current_part.coord.0 != -1 {
+            if current_part.coord.x != -1 {
                 parts.push(current_part.clone());
                 current_part = Part {
                     number: String::with_capacity(3),
-                    coord: Point(-1, line_index, false),
+                    coord: Point::new(-1, line_index),
                 };
             }
         }
     }
 
-    if current_part.coord.0 != -1 {
+    if current_part.coord.x != -1 {
         parts.push(current_part.clone());
     }
 
-    (points, parts)
+    (symbols, parts)
 }
 
-fn get_parts_and_points(source: &str) -> (Vec<Point>, Vec<Part>) {
-    let mut points = Vec::with_capacity(1200);
+fn get_parts_and_symbols(schematic: &Grid<char>) -> (Vec<Symbol>, Vec<Part>) {
+    let mut symbols = Vec::with_capacity(1200);
     let mut parts = Vec::with_capacity(1200);
 
-    for (line_index, line) in source.split('\n').enumerate() {
-        let (new_points, new_parts) = parse_line(line, line_index as i32);
-        points.extend(new_points);
+    for (line_index, row) in schematic.rows().enumerate() {
+        let (new_symbols, new_parts) = parse_row(row, line_index as i32);
+        symbols.extend(new_symbols);
         parts.extend(new_parts);
     }
 
-    (points, parts)
+    (symbols, parts)
 }
 
 #[cfg(test)]
@@ -113,45 +172,62 @@ mod tests {
     use super::*;
     use crate::utils::read_input;
 
+    fn schematic(source: &str) -> Grid<char> {
+        Grid::parse(source, Ok).unwrap()
+    }
+
+    fn symbol(x: i32, y: i32, gear: bool) -> Symbol {
+        Symbol {
+            position: Point::new(x, y),
+            gear,
+        }
+    }
+
     #[test]
-    fn test_parse_line() {
+    fn test_parse_row() {
         let parts = vec![Part {
             number: String::from("123"),
-            coord: Point(0, 0, false),
+            coord: Point::new(0, 0),
         }];
-        assert_eq!(parse_line("123..*", 0), (vec![Point(5, 0, true)], parts));
+        assert_eq!(
+            parse_row(schematic("123..*").row(0).unwrap(), 0),
+            (vec![symbol(5, 0, true)], parts)
+        );
     }
 
     #[test]
-    fn test_parse_line_2() {
+    fn test_parse_row_2() {
         let parts = vec![
             Part {
                 number: String::from("123"),
-                coord: Point(0, 0, false),
+                coord: Point::new(0, 0),
             },
             Part {
                 number: String::from("456"),
-                coord: Point(5, 0, false),
+                coord: Point::new(5, 0),
             },
         ];
-        assert_eq!(parse_line("123..456", 0), (vec![], parts));
+        assert_eq!(
+            parse_row(schematic("123..456").row(0).unwrap(), 0),
+            (vec![], parts)
+        );
     }
 
     #[test]
-    fn test_parse_lines() {
+    fn test_parts_and_symbols() {
         let parts = vec![
             Part {
                 number: String::from("123"),
-                coord: Point(0, 0, false),
+                coord: Point::new(0, 0),
             },
             Part {
                 number: String::from("456"),
-                coord: Point(1, 1, false),
+                coord: Point::new(1, 1),
             },
         ];
         assert_eq!(
-            get_parts_and_points("123...*\n.456.#."),
-            (vec![Point(6, 0, true), Point(5, 1, false)], parts)
+            get_parts_and_symbols(&schematic("123...*\n.456.#.")),
+            (vec![symbol(6, 0, true), symbol(5, 1, false)], parts)
         )
     }
 
@@ -159,41 +235,51 @@ mod tests {
     fn test_in_range() {
         let part = Part {
             number: String::from("123"),
-            coord: Point(0, 0, false),
+            coord: Point::new(0, 0),
         };
         // In Range
-        assert!(part.in_range(&Point(0, 1, false))); // 1 below
-        assert!(part.in_range(&Point(-1, 0, false))); // 1 left
-        assert!(part.in_range(&Point(3, 0, false))); // 1 right
-        assert!(part.in_range(&Point(0, -1, false))); // 1 above
+        assert!(part.in_range(Point::new(0, 1))); // 1 below
+        assert!(part.in_range(Point::new(-1, 0))); // 1 left
+        assert!(part.in_range(Point::new(3, 0))); // 1 right
+        assert!(part.in_range(Point::new(0, -1))); // 1 above
 
-        assert!(part.in_range(&Point(-1, -1, false))); // diagonal
-        assert!(part.in_range(&Point(-1, 1, false))); // diagonal
-        assert!(part.in_range(&Point(3, -1, false))); // diagonal
-        assert!(part.in_range(&Point(3, 1, false))); // diagonal
+        assert!(part.in_range(Point::new(-1, -1))); // diagonal
+        assert!(part.in_range(Point::new(-1, 1))); // diagonal
+        assert!(part.in_range(Point::new(3, -1))); // diagonal
+        assert!(part.in_range(Point::new(3, 1))); // diagonal
 
         // Out of Range
-        assert!(!part.in_range(&Point(0, 2, false))); // 2 below
-        assert!(!part.in_range(&Point(-2, 0, false))); // 2 left
-        assert!(!part.in_range(&Point(4, 0, false))); // 2 right
-        assert!(!part.in_range(&Point(0, -2, false))); // 2 above
-
-        assert!(!part.in_range(&Point(-2, -1, false))); // diagonal
-        assert!(!part.in_range(&Point(-2, 1, false))); // diagonal
-        assert!(!part.in_range(&Point(4, -1, false))); // diagonal
-        assert!(!part.in_range(&Point(4, 1, false))); // diagonal
-
-        assert!(!part.in_range(&Point(-1, -2, false))); // diagonal
-        assert!(!part.in_range(&Point(-1, 2, false))); // diagonal
-        assert!(!part.in_range(&Point(3, -2, false))); // diagonal
-        assert!(!part.in_range(&Point(3, 2, false))); // diagonal
+        assert!(!part.in_range(Point::new(0, 2))); // 2 below
+        assert!(!part.in_range(Point::new(-2, 0))); // 2 left
+        assert!(!part.in_range(Point::new(4, 0))); // 2 right
+        assert!(!part.in_range(Point::new(0, -2))); // 2 above
+
+        assert!(!part.in_range(Point::new(-2, -1))); // diagonal
+        assert!(!part.in_range(Point::new(-2, 1))); // diagonal
+        assert!(!part.in_range(Point::new(4, -1))); // diagonal
+        assert!(!part.in_range(Point::new(4, 1))); // diagonal
+
+        assert!(!part.in_range(Point::new(-1, -2))); // diagonal
+        assert!(!part.in_range(Point::new(-1, 2))); // diagonal
+        assert!(!part.in_range(Point::new(3, -2))); // diagonal
+        assert!(!part.in_range(Point::new(3, 2))); // diagonal
+    }
+
+    #[test]
+    fn test_long_part_number() {
+        let error = Day3::parse(&PuzzleInput::new("#...........\n.12345678901")).unwrap_err();
+
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 2, "12345678901")
+        );
     }
 
     #[test]
     fn test_part_value() {
         let part = Part {
             number: String::from("123"),
-            coord: Point(0, 0, false),
+            coord: Point::new(0, 0),
         };
 
         assert_eq!(part.value(), 123);
@@ -201,44 +287,38 @@ mod tests {
 
     #[test]
     fn test_example_1() {
-        assert_eq!(exec(&String::from("123.*")).0, 0);
-        assert_eq!(exec(&String::from("123*.")).0, 123);
-        assert_eq!(exec(&String::from("123..\n*....")).0, 123);
-        assert_eq!(exec(&String::from("123..\n.....\n*....")).0, 0);
-        assert_eq!(exec(&String::from("123..\n...*.\n.....")).0, 123);
-        assert_eq!(exec(&String::from("123..\n...*.\n..456")).0, 579);
-        assert_eq!(exec(&String::from("123..\n.*.*.\n..456")).0, 579);
-        assert_eq!(exec(&String::from("123..\n....*\n..456")).0, 456);
-        assert_eq!(exec(&String::from("111\n*$*\n1.1")).0, 113);
-        assert_eq!(exec(&String::from("111\n*..\n1.1")).0, 112);
-        assert_eq!(exec(&String::from("111\n...\n1*1")).0, 2);
-    }
-
-    #[test]
-    fn test_sample_data() {
-        let sample_data = read_input("3_sample_1");
-        assert_eq!(exec(&sample_data).0, 4361);
+        assert_eq!(Day3::exec("123.*").unwrap().0, 0);
+        assert_eq!(Day3::exec("123*.").unwrap().0, 123);
+        assert_eq!(Day3::exec("123..\n*....").unwrap().0, 123);
+        assert_eq!(Day3::exec("123..\n.....\n*....").unwrap().0, 0);
+        assert_eq!(Day3::exec("123..\n...*.\n.....").unwrap().0, 123);
+        assert_eq!(Day3::exec("123..\n...*.\n..456").unwrap().0, 579);
+        assert_eq!(Day3::exec("123..\n.*.*.\n..456").unwrap().0, 579);
+        assert_eq!(Day3::exec("123..\n....*\n..456").unwrap().0, 456);
+        assert_eq!(Day3::exec("111\n*$*\n1.1").unwrap().0, 113);
+        assert_eq!(Day3::exec("111\n*..\n1.1").unwrap().0, 112);
+        assert_eq!(Day3::exec("111\n...\n1*1").unwrap().0, 2);
     }
 
     #[test]
     fn test_validate_first_three_lines() {
         let data = read_input("3");
-        let sample_data = data.split("\n").collect::<Vec<&str>>();
+        let sample_data = data.split('\n').collect::<Vec<&str>>();
 
         let first_3_lines = &sample_data[..3].join("\n");
 
-        assert_eq!(exec(&first_3_lines).0, 8264);
+        assert_eq!(Day3::exec(first_3_lines).unwrap().0, 8264);
     }
 
     #[test]
     fn test_excluded_parts() {
         let sample_data = read_input("3_sample_1");
-        let (points, parts) = get_parts_and_points(&sample_data);
+        let (symbols, parts) = get_parts_and_symbols(&schematic(&sample_data));
 
         let mut excluded_parts: Vec<Part> = vec![];
 
         parts.iter().for_each(|part| {
-            if points.iter().any(|point| part.in_range(&point)) {
+            if symbols.iter().any(|symbol| part.in_range(symbol.position)) {
             } else {
                 excluded_parts.push(part.clone())
             };
@@ -249,19 +329,13 @@ mod tests {
             vec![
                 Part {
                     number: String::from("114"),
-                    coord: Point(5, 0, false),
+                    coord: Point::new(5, 0),
                 },
                 Part {
                     number: String::from("58"),
-                    coord: Point(7, 5, false),
+                    coord: Point::new(7, 5),
                 },
             ]
         )
     }
-
-    #[test]
-    fn test_sample_part_2() {
-        let data = read_input("3_sample_1");
-        assert_eq!(exec(&data).1, 467835)
-    }
 }
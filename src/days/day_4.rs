@@ -1,54 +1,51 @@
+//! [Day 4: Scratchcards](https://adventofcode.com/2023/day/4)
+
 use std::collections::{HashMap, HashSet};
 
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::parse::{ints, key_value, split_once_or_err, Cursor};
+
+/// A scratchcard, reduced to how many of its numbers are winning ones.
 #[derive(PartialEq, Clone, Debug)]
-struct Card {
-    id: u32,
-    matches: u32,
+pub struct Card {
+    /// The card's number.
+    pub id: u32,
+    /// How many of the numbers you have are winning numbers.
+    pub matches: u32,
 }
 
-pub fn exec(source: &str) -> (u32, u32) {
-    let cards: Vec<_> = source
-        .split('\n')
-        .enumerate()
-        .map(|(index, line)| parse_line(index as u32 + 1, line))
-        .collect();
-
-    (
-        cards.iter().map(|card| calc_score(&card)).sum(),
-        calc_part_2(&cards),
-    )
-}
+/// Scores each card by its winning numbers, then counts the copies won.
+pub struct Day4;
 
-fn parse_line(id: u32, line: &str) -> Card {
-    let (_, game) = line.split_once(": ").unwrap();
-    let (ticket_raw, win_raw) = game.split_once(" | ").unwrap();
-    let matches = extract_numbers(ticket_raw)
-        .intersection(&extract_numbers(win_raw))
-        .count() as u32;
+impl Solution for Day4 {
+    type Input = Vec<Card>;
 
-    Card { id, matches }
-}
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        input.lines().map(parse_line).collect()
+    }
 
-fn extract_numbers(number_string: &str) -> HashSet<u32> {
-    let mut result = HashSet::new();
-    let mut current_number = String::new();
-
-    for char in number_string.trim().chars() {
-        if char.is_ascii_digit() {
-            current_number.push(char);
-        } else {
-            if current_number.as_str() != "" {
-                result.insert(current_number.parse().unwrap());
-                current_number = String::new();
-            }
-        }
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(input.iter().map(calc_score).sum::<u32>().into())
     }
 
-    if current_number.as_str() != "" {
-        result.insert(current_number.parse().unwrap());
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(calc_part_2(input).into())
     }
+}
 
-    result
+fn parse_line<'a>(line: impl Into<Cursor<'a>>) -> Result<Card, ParseError> {
+    let (card, numbers) = key_value(line)?;
+    let [id] = ints(card)?[..] else {
+        return Err(card.error("expected 'Card <id>'"));
+    };
+    let (ticket, winning) = split_once_or_err(numbers, " | ", "expected '<numbers> | <numbers>'")?;
+    let ticket = ints::<u32>(ticket)?.into_iter().collect::<HashSet<_>>();
+    let winning = ints::<u32>(winning)?.into_iter().collect::<HashSet<_>>();
+    let matches = ticket.intersection(&winning).count() as u32;
+
+    Ok(Card { id, matches })
 }
 
 fn calc_score(card: &Card) -> u32 {
@@ -59,7 +56,7 @@ fn calc_score(card: &Card) -> u32 {
     }
 }
 
-fn calc_part_2(cards: &Vec<Card>) -> u32 {
+fn calc_part_2(cards: &[Card]) -> u32 {
     let mut card_counts = cards
         .iter()
         .map(|card| (card.id, 1_u32))
@@ -69,7 +66,7 @@ fn calc_part_2(cards: &Vec<Card>) -> u32 {
         let current = *card_counts.get(&card.id).unwrap();
 
         if card.matches > 0 {
-            for x in &card.id + 1..=&card.id + card.matches {
+            for x in card.id + 1..=card.id + card.matches {
                 *card_counts.entry(x).or_insert(1) += current;
             }
         }
@@ -81,48 +78,38 @@ fn calc_part_2(cards: &Vec<Card>) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::read_input;
-
     #[test]
     fn test_parse_line() {
         let sample = "Game 1: 1 2 3 | 1 3";
-        let result = parse_line(1, sample);
+        let result = parse_line(sample).unwrap();
         assert_eq!(result.matches, 2);
     }
 
     #[test]
     fn test_parse_line_2() {
         let sample = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let result = parse_line(1, sample);
+        let result = parse_line(sample).unwrap();
         assert_eq!(result.matches, 4);
-        assert_eq!(exec(sample).0, 8);
-    }
-
-    #[test]
-    fn test_extract_numbers() {
-        assert_eq!(extract_numbers("1 2 3"), HashSet::from([1, 2, 3]));
-        assert_eq!(extract_numbers(" 1 2 3"), HashSet::from([1, 2, 3]));
-        assert_eq!(extract_numbers("1 2 3 "), HashSet::from([1, 2, 3]));
-        assert_eq!(extract_numbers("1 2  3"), HashSet::from([1, 2, 3]));
-        assert_eq!(extract_numbers(" 1    2  3"), HashSet::from([1, 2, 3]));
-        assert_eq!(extract_numbers("1    2  3  "), HashSet::from([1, 2, 3]));
-    }
-
-    #[test]
-    fn test_sample_data_1() {
-        let sample_data = read_input("4_sample_1");
-        assert_eq!(exec(&sample_data).0, 13);
+        assert_eq!(Day4::exec(sample).unwrap().0, 8);
     }
 
     #[test]
-    fn test_sample_data_2() {
-        let sample_data = read_input("4_sample_1");
-        assert_eq!(exec(&sample_data).1, 30);
+    fn test_parse_line_errors() {
+        assert_eq!(parse_line("Card 1 1 2 | 3").unwrap_err().column, 1);
+        assert_eq!(parse_line("Card 1: 1 2 3").unwrap_err().column, 9);
+        assert_eq!(parse_line("Card: 1 | 2").unwrap_err().text, "Card");
+        assert_eq!(parse_line("Card  12: 1 | 1").map(|card| card.id), Ok(12));
+        assert_eq!(
+            Day4::parse(&PuzzleInput::new("Card 1: 1 | 2\nCard 2 1 | 2"))
+                .unwrap_err()
+                .line,
+            2
+        );
     }
 
     #[test]
     fn test_2_example() {
-        let sample = vec![
+        let sample = [
             Card { id: 1, matches: 2 },
             Card { id: 2, matches: 1 },
             Card { id: 3, matches: 0 },
@@ -1,157 +1,82 @@
-#[derive(Clone, PartialEq, Debug)]
-struct Map {
-    range: Range,
-    offset: isize,
-}
-
-impl Map {
-    fn offset(&self, value: usize) -> usize {
-        return (value as isize + self.offset) as usize;
-    }
-}
-
-type Range = (usize, usize);
-type MapVec = Vec<Vec<Map>>;
-
-pub fn exec(source: &str) -> Range {
-    let (seeds, maps) = parse_input(source);
+//! [Day 5: If You Give A Seed A Fertilizer](https://adventofcode.com/2023/day/5)
 
-    let part_1_seeds: Vec<Range> = seeds.iter().map(|seed| (*seed, *seed)).collect();
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::interval::{Interval, IntervalMap, RangeSet};
+use crate::utils::parse::BlockCursor;
 
-    let mut part_1 = vec![];
+/// The almanac's maps in order, from seed to location.
+pub type MapVec = Vec<IntervalMap<usize>>;
 
-    part_1_seeds.iter().for_each(|seed| {
-        part_1.extend(process_maps(*seed, &maps));
-    });
+/// Follows each seed through the almanac's maps to the lowest location, first
+/// for single seeds and then for ranges of them.
+pub struct Day5;
 
-    let part_2_seeds: Vec<Range> = seeds
-        .chunks(2)
-        .map(|item| {
-            (
-                *item.get(0).unwrap(),
-                *item.get(0).unwrap() + *item.get(1).unwrap() - 1,
-            )
-        })
-        .collect();
-
-    let mut part_2 = vec![];
-
-    part_2_seeds.iter().for_each(|seed| {
-        part_2.extend(process_maps(*seed, &maps));
-    });
-
-    (
-        part_1.iter().map(|thing| thing.0).min().unwrap(),
-        part_2.iter().map(|thing| thing.0).min().unwrap(),
-    )
-}
+impl Solution for Day5 {
+    type Input = (Vec<usize>, MapVec);
 
-fn parse_input(source: &str) -> (Vec<usize>, MapVec) {
-    let mut seeds: Vec<usize> = vec![];
-    let mut maps: MapVec = vec![];
-    let mut current_map = vec![];
-
-    for line in source.split('\n') {
-        if line.starts_with("seeds: ") {
-            let (_, seeds_str) = line.split_once(": ").unwrap();
-            seeds_str
-                .split(" ")
-                .for_each(|seed| seeds.push(seed.parse().unwrap()));
-        } else if line.ends_with(" map:") {
-            if current_map.iter().count() > 0 {
-                maps.push(current_map.clone());
-                current_map = vec![];
-            }
-        } else if line.len() > 0 {
-            let mut parts = line.split(' ');
-            let finish: usize = parts.next().unwrap().parse().unwrap();
-            let start: usize = parts.next().unwrap().parse().unwrap();
-            let range: usize = parts.next().unwrap().parse().unwrap();
-
-            current_map.push(Map {
-                range: (start, start + range),
-                offset: finish as isize - start as isize,
-            })
-        }
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    if current_map.iter().count() > 0 {
-        maps.push(current_map.clone());
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        let (seeds, maps) = input;
+        let seeds = seeds
+            .iter()
+            .map(|&seed| Interval::with_length(seed, 1))
+            .collect();
+
+        Ok(lowest_location(seeds, maps)?.into())
     }
 
-    (seeds, maps)
-}
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        let (seeds, maps) = input;
+        if seeds.len() % 2 != 0 {
+            return Err(Error::solve("seeds must come in start and length pairs"));
+        }
 
-fn process_maps(seed: Range, maps: &MapVec) -> Vec<Range> {
-    let mut to_work = vec![seed];
-    let mut result = vec![];
+        let seeds = seeds
+            .chunks(2)
+            .map(|item| Interval::with_length(item[0], item[1]))
+            .collect();
 
-    for map in maps {
-        for work in &to_work {
-            result.extend(process_map(*work, &map));
-        }
-        to_work = result.clone();
-        result = vec![];
+        Ok(lowest_location(seeds, maps)?.into())
     }
+}
 
-    to_work
+fn lowest_location(seeds: RangeSet<usize>, maps: &MapVec) -> Result<usize, Error> {
+    maps.iter()
+        .fold(seeds, |values, map| map.apply_set(&values))
+        .min()
+        .ok_or_else(|| Error::solve("no seeds to plant"))
 }
 
-fn process_map(seed: Range, maps: &Vec<Map>) -> Vec<Range> {
-    let mut devoured = false;
-    let mut chomped = vec![];
-
-    let (mut min, mut max) = seed;
-
-    // 4 scenarios per chomp
-    // |-----<==============>------| Seed Range
-    // |--|===========|------------| 1
-    // |--|===================|----| 2
-    // |-------------|========|----| 3
-    // |--------|======|-----------| 4
-
-    for map in maps {
-        if min >= map.range.0 && min <= map.range.1 {
-            if max > map.range.1 {
-                // Scenario 1
-                chomped.push((map.offset(min), map.offset(map.range.1)));
-                if max + 1 == map.range.1 {
-                    devoured = true;
-                    break;
-                }
-                min = map.range.1;
-            } else {
-                // Scenario 2
-                chomped.push((map.offset(min), map.offset(max)));
-                devoured = true;
-                // Completely swallowed -- break
-                break;
-            }
-        } else if max >= map.range.0 && max < map.range.1 {
-            // Scenario 3
-            if min <= map.range.0 {
-                chomped.push((map.offset(map.range.0), map.offset(max)));
-                if min == map.range.0 {
-                    devoured = true;
-                    break;
-                }
-                max = map.range.0 - 1;
-            } else {
-                // Scenario 4
-                chomped.push((map.offset(map.range.0), map.offset(map.range.1)));
-                // Splits range into 2 distinct parts
-                // Check each
-                chomped.extend(process_map((min, map.range.0 - 1), &maps));
-                chomped.extend(process_map((max + 1, map.range.1), &maps));
+fn parse_input(input: &PuzzleInput) -> Result<(Vec<usize>, MapVec), ParseError> {
+    let mut blocks = BlockCursor::new(input);
+    let mut seeds = blocks.next_block("the seeds")?.lines();
+    let seeds_line = seeds.next_line("the seeds")?;
+    seeds.finish()?;
+    let seeds = seeds_line.strip_prefix("seeds: ")?.numbers()?;
+
+    let maps = blocks
+        .map(|block| {
+            let mut lines = block.lines();
+            lines.next_line("a map")?.strip_suffix(" map:")?;
+
+            let mut map = IntervalMap::new();
+            for line in lines {
+                let [destination, source, length] = line.numbers()?[..] else {
+                    return Err(line.error("expected '<destination> <source> <length>'"));
+                };
+                map.insert(Interval::with_length(source, length), destination);
             }
-        }
-    }
 
-    if !devoured {
-        chomped.push((min, max))
-    }
+            Ok(map)
+        })
+        .collect::<Result<_, _>>()?;
 
-    chomped
+    Ok((seeds, maps))
 }
 
 #[cfg(test)]
@@ -162,87 +87,84 @@ mod tests {
 
     #[test]
     fn test_parse_maps() {
-        let (seeds, maps) = parse_input(&read_input("5_sample_1"));
+        let (seeds, maps) = parse_input(&PuzzleInput::new(&read_input("5_sample_1"))).unwrap();
 
         assert_eq!(seeds, vec![79, 14, 55, 13]);
 
-        let seed_to_soil = maps.get(0).unwrap().clone();
         assert_eq!(
-            seed_to_soil,
-            vec![
-                Map {
-                    range: (98, 100),
-                    offset: -48,
-                },
-                Map {
-                    range: (50, 98),
-                    offset: 2,
-                },
-            ]
+            maps[0].pieces(),
+            &[(Interval::new(50, 98), 52), (Interval::new(98, 100), 50)]
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input(&PuzzleInput::new("seeds: 1 x")).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+
+        let error =
+            parse_input(&PuzzleInput::new("seeds: 1 2\n\nseed-to-soil map:\n1 2")).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+    }
+
+    #[test]
+    fn test_odd_seed_count() {
+        assert!(Day5::exec("seeds: 1 2 3\n\nseed-to-soil map:\n1 2 3").is_err());
+    }
+
     #[test]
     fn test_walk_map_single_number() {
-        let maps = vec![
-            Map {
-                range: (98, 99),
-                offset: -48,
-            },
-            Map {
-                range: (50, 97),
-                offset: 2,
-            },
-        ];
-
-        // Seed Ranges
-        let result = process_map((79, 79), &maps);
-        assert_eq!(result, vec![(81, 81)]);
-        let result = process_map((14, 14), &maps);
-        assert_eq!(result, vec![(14, 14)]);
-        let result = process_map((55, 55), &maps);
-        assert_eq!(result, vec![(57, 57)]);
-        let result = process_map((13, 13), &maps);
-        assert_eq!(result, vec![(13, 13)]);
+        let mut map = IntervalMap::new();
+        map.insert(Interval::with_length(98, 2), 50);
+        map.insert(Interval::with_length(50, 48), 52);
+
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(14), 14);
+        assert_eq!(map.apply(55), 57);
+        assert_eq!(map.apply(13), 13);
         // Edge of range
-        let result = process_map((99, 99), &maps);
-        assert_eq!(result, vec![(51, 51)]);
-        let result = process_map((97, 97), &maps);
-        assert_eq!(result, vec![(99, 99)]);
+        assert_eq!(map.apply(99), 51);
+        assert_eq!(map.apply(97), 99);
+        assert_eq!(map.apply(100), 100);
     }
 
     #[test]
     fn test_each_seed_in_sample() {
         let sample = read_input("5_sample_1");
-        let (_, maps) = parse_input(&sample);
+        let (_, maps) = parse_input(&PuzzleInput::new(&sample)).unwrap();
 
         let results = [13, 52, 41, 34, 34, 35, 35];
-        let mut seed = (13, 13);
+        let mut seed = 13;
 
         for (index, map) in maps.iter().enumerate() {
-            seed = *process_map(seed, map).iter().next().unwrap();
-            assert_eq!(seed.0, *results.get(index).unwrap());
-            assert_eq!(seed.1, *results.get(index).unwrap());
+            seed = map.apply(seed);
+            assert_eq!(seed, results[index]);
         }
     }
 
     #[test]
-    fn test_example() {
-        let sample = String::from("seeds: 1 10\n\nseed-to-location map:\n10 1 1");
-
-        assert_eq!(exec(&sample).0, 10)
-    }
-
-    #[test]
-    fn test_sample_1() {
+    fn test_seed_range_split_across_maps() {
         let sample = read_input("5_sample_1");
-        assert_eq!(exec(&sample).0, 35)
+        let (_, maps) = parse_input(&PuzzleInput::new(&sample)).unwrap();
+        let almanac = maps
+            .iter()
+            .fold(IntervalMap::new(), |almanac, map| almanac.compose(map));
+
+        for seed in 0..110 {
+            let expected = maps.iter().fold(seed, |value, map| map.apply(value));
+            assert_eq!(almanac.apply(seed), expected, "seed {seed}");
+        }
+        assert_eq!(
+            lowest_location(RangeSet::from(Interval::with_length(82, 1)), &maps),
+            Ok(46)
+        );
     }
 
     #[test]
-    fn test_sample_2() {
-        let sample = read_input("5_sample_1");
-        assert_eq!(exec(&sample).1, 46)
+    fn test_example() {
+        let sample = String::from("seeds: 1 10\n\nseed-to-location map:\n10 1 1");
+
+        assert_eq!(Day5::exec(&sample).unwrap().0, 10)
     }
 
     #[test]
@@ -273,7 +195,7 @@ humidity-to-location map:
 0 74 1",
         );
 
-        assert_eq!(exec(&sample).1, 0)
+        assert_eq!(Day5::exec(&sample).unwrap().1, 0)
     }
 
     #[test]
@@ -303,6 +225,6 @@ humidity-to-location map:
 0 1 1",
         );
 
-        assert_eq!(exec(&sample).1, 0)
+        assert_eq!(Day5::exec(&sample).unwrap().1, 0)
     }
 }
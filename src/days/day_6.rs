@@ -1,10 +1,66 @@
-const INPUT_1: [(usize, usize); 4] = [(54, 239), (70, 1142), (82, 1295), (75, 1253)];
+//! [Day 6: Wait For It](https://adventofcode.com/2023/day/6)
 
-pub fn exec(_source: &str) -> (usize, usize) {
-    (
-        INPUT_1.iter().map(|(t, d)| math_solve(*t, *d)).product(),
-        math_solve(54708275, 239114212951253),
-    )
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::math::isqrt;
+
+/// Counts the ways to beat each race's record distance, then for the one
+/// long race the sheet describes once its spacing is ignored.
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<(usize, usize)>;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        parse_races(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(input
+            .iter()
+            .map(|(t, d)| math_solve(*t, *d))
+            .product::<usize>()
+            .into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        let time = join_digits(input.iter().map(|(t, _)| *t));
+        let distance = join_digits(input.iter().map(|(_, d)| *d));
+
+        match (time, distance) {
+            (Some(time), Some(distance)) => Ok(math_solve(time, distance).into()),
+            _ => Err(Error::solve("the race is too long to count")),
+        }
+    }
+}
+
+fn parse_races(input: &PuzzleInput) -> Result<Vec<(usize, usize)>, ParseError> {
+    let mut lines = input.lines();
+    let times_line = lines.next_line("the race times")?;
+    let distances_line = lines.next_line("the record distances")?;
+    lines.finish()?;
+
+    let times = times_line.strip_prefix("Time:")?.numbers::<usize>()?;
+    if times.is_empty() {
+        return Err(times_line.error("expected at least one race"));
+    }
+    let distances = distances_line.strip_prefix("Distance:")?.numbers()?;
+    if times.len() != distances.len() {
+        return Err(distances_line.error(format!("expected {} distances", times.len())));
+    }
+
+    Ok(times.into_iter().zip(distances).collect())
+}
+
+/// The number written by running the digits of `values` together, or `None`
+/// if it doesn't fit.
+fn join_digits(values: impl Iterator<Item = usize>) -> Option<usize> {
+    values
+        .map(|value| value.to_string())
+        .collect::<String>()
+        .parse()
+        .ok()
 }
 
 /*fn evaluate_options(time: usize, distance: usize) -> usize {
@@ -31,13 +87,31 @@ pub fn exec(_source: &str) -> (usize, usize) {
     }
 }*/
 
+/// The number of whole hold times that beat `distance` in a race lasting
+/// `time`, worked in `u128` so that squaring the time can't overflow.
 fn math_solve(time: usize, distance: usize) -> usize {
-    let d = f64::sqrt((time * time - 4 * distance) as f64);
+    let (time, distance) = (time as u128, distance as u128);
+    let beats = |hold: u128| hold * (time - hold) > distance;
+    let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+        return 0;
+    };
 
-    let from = f64::floor((time as f64 - d) / 2_f64);
-    let to = f64::ceil((time as f64 + d) / 2_f64);
+    // Start near the lower root of `hold * (time - hold) = distance` and
+    // step to the first hold that wins. The winners are symmetric about
+    // half the time.
+    let mut first = (time - isqrt(discriminant)) / 2;
+    while first > 0 && beats(first - 1) {
+        first -= 1;
+    }
+    while first <= time / 2 && !beats(first) {
+        first += 1;
+    }
 
-    (to - from) as usize - 1
+    if first > time / 2 {
+        0
+    } else {
+        (time - 2 * first + 1) as usize
+    }
 }
 
 #[cfg(test)]
@@ -49,5 +123,36 @@ mod tests {
         assert_eq!(math_solve(7, 9), 4);
         assert_eq!(math_solve(15, 40), 8);
         assert_eq!(math_solve(30, 200), 9);
+        assert_eq!(math_solve(3, 9), 0);
+    }
+
+    #[test]
+    fn test_long_race_does_not_overflow() {
+        let sheet = "Time:      54    70    82    75    99\nDistance: 239  1142  1295  1253   100";
+
+        assert_eq!(
+            Day6::exec(sheet),
+            Ok((76_826_880.into(), 5_382_703_574_usize.into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_races() {
+        let input = PuzzleInput::new("Time:      7  15\nDistance:  9  40");
+        assert_eq!(parse_races(&input), Ok(vec![(7, 9), (15, 40)]));
+        assert_eq!(join_digits([7, 15, 30].into_iter()), Some(71530));
+
+        let error = parse_races(&PuzzleInput::new("Time: 7 15\nDistance: 9")).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            parse_races(&PuzzleInput::new("Time: 7")).unwrap_err().line,
+            2
+        );
+        assert_eq!(
+            parse_races(&PuzzleInput::new("Time: 7\nDistance: x"))
+                .unwrap_err()
+                .column,
+            11
+        );
     }
 }
@@ -1,33 +1,49 @@
+//! [Day 7: Camel Cards](https://adventofcode.com/2023/day/7)
+
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::parse::{split_once_or_err, Cursor};
+
 const FACE_VALUE: [(char, usize); 5] = [('A', 14), ('K', 13), ('Q', 12), ('J', 11), ('T', 10)];
 
-pub fn exec(source: &str) -> (usize, usize) {
-    let hand_lookup = HashMap::from(FACE_VALUE);
+/// Ranks the hands to total their winnings, with `J` as a joker in part 2.
+pub struct Day7;
 
-    let mut hands = source
-        .split('\n')
-        .map(|line| parse_line(line, &hand_lookup))
-        .collect::<Vec<_>>();
+impl Solution for Day7 {
+    type Input = Vec<Hand>;
 
-    hands.sort_by(|a, b| sort_cards(a, b, &GameMode::Standard));
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        let hand_lookup = HashMap::from(FACE_VALUE);
 
-    let part_1 = hands
-        .iter()
-        .enumerate()
-        .map(|(rank, hand)| hand.bet * (rank + 1))
-        .sum();
+        input
+            .lines()
+            .map(|line| parse_line(line, &hand_lookup))
+            .collect()
+    }
 
-    hands.sort_by(|a, b| sort_cards(a, b, &GameMode::Joker));
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(total_winnings(input, &GameMode::Standard).into())
+    }
 
-    let part_2 = hands
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(total_winnings(input, &GameMode::Joker).into())
+    }
+}
+
+fn total_winnings(hands: &[Hand], mode: &GameMode) -> usize {
+    let mut hands = hands.iter().collect::<Vec<_>>();
+
+    hands.sort_by(|a, b| sort_cards(a, b, mode));
+
+    hands
         .iter()
         .enumerate()
         .map(|(rank, hand)| hand.bet * (rank + 1))
-        .sum();
-
-    (part_1, part_2)
+        .sum()
 }
 
 #[derive(PartialEq, Debug)]
@@ -36,10 +52,13 @@ enum GameMode {
     Joker,
 }
 
+/// A hand of cards and its bid.
 #[derive(PartialEq, Debug)]
-struct Hand {
-    cards: [usize; 5],
-    bet: usize,
+pub struct Hand {
+    /// Card values from 2 to 14, with `T` as 10 and `A` as 14.
+    pub cards: [usize; 5],
+    /// The bid the hand's rank is multiplied by.
+    pub bet: usize,
 }
 
 impl Hand {
@@ -62,7 +81,7 @@ impl Hand {
         counts.sort();
         counts.reverse();
 
-        let (mut count_1, best_card) = *counts.get(0).unwrap();
+        let (mut count_1, best_card) = counts[0];
         let (mut count_2, next_card) = *counts.get(1).unwrap_or(&(0, 0));
 
         if mode == &GameMode::Joker {
@@ -86,7 +105,7 @@ impl Hand {
 }
 
 fn sort_cards(a: &Hand, b: &Hand, mode: &GameMode) -> Ordering {
-    match a.hand_rank(&mode).cmp(&b.hand_rank(&mode)) {
+    match a.hand_rank(mode).cmp(&b.hand_rank(mode)) {
         Ordering::Less => Ordering::Less,
         Ordering::Greater => Ordering::Greater,
         Ordering::Equal => {
@@ -103,54 +122,62 @@ fn sort_cards(a: &Hand, b: &Hand, mode: &GameMode) -> Ordering {
     }
 }
 
-fn parse_line(line: &str, hand_lookup: &HashMap<char, usize>) -> Hand {
-    let (left, right) = line.split_once(' ').unwrap();
-    let bet = right.parse().unwrap();
+fn parse_line<'a>(
+    line: impl Into<Cursor<'a>>,
+    hand_lookup: &HashMap<char, usize>,
+) -> Result<Hand, ParseError> {
+    let (cards, bet) = split_once_or_err(line, " ", "expected '<cards> <bet>'")?;
+    let bet = bet.number()?;
 
-    let cards = left
+    let cards = cards
         .chars()
-        .map(|char| match hand_lookup.get(&char) {
-            None => char.to_digit(10).unwrap() as usize,
-            Some(value) => *value,
+        .map(|(char, at)| match hand_lookup.get(&char) {
+            None => char
+                .to_digit(10)
+                .map(|digit| digit as usize)
+                .ok_or_else(|| at.error("unknown card")),
+            Some(value) => Ok(*value),
         })
-        .collect::<Vec<_>>()
+        .collect::<Result<Vec<_>, _>>()?
         .try_into()
-        .unwrap();
+        .map_err(|_| cards.error("expected five cards"))?;
 
-    Hand { cards, bet }
+    Ok(Hand { cards, bet })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_input;
-
     use super::*;
 
-    #[test]
-    fn test_sample() {
-        let sample = read_input("7_sample_1");
-        assert_eq!(exec(&sample), (6440, 5905))
-    }
-
     #[test]
     fn test_parse_line() {
         assert_eq!(
             parse_line("12345 678", &HashMap::from(FACE_VALUE)),
-            Hand {
+            Ok(Hand {
                 cards: [1, 2, 3, 4, 5],
                 bet: 678,
-            }
+            })
         );
     }
 
+    #[test]
+    fn test_parse_line_errors() {
+        let lookup = HashMap::from(FACE_VALUE);
+
+        assert_eq!(parse_line("22345", &lookup).unwrap_err().column, 1);
+        assert_eq!(parse_line("22345 x", &lookup).unwrap_err().column, 7);
+        assert_eq!(parse_line("22X45 1", &lookup).unwrap_err().column, 3);
+        assert_eq!(parse_line("2234 1", &lookup).unwrap_err().text, "2234");
+    }
+
     #[test]
     fn test_parse_line_with_faces() {
         assert_eq!(
             parse_line("TJA4K 678", &HashMap::from(FACE_VALUE)),
-            Hand {
+            Ok(Hand {
                 cards: [10, 11, 14, 4, 13],
                 bet: 678,
-            }
+            })
         );
     }
 
@@ -216,7 +243,7 @@ mod tests {
 
     #[test]
     fn test_sort_hands() {
-        let mut hands = vec![
+        let mut hands = [
             Hand {
                 cards: [13, 13, 13, 13, 13],
                 bet: 1,
@@ -1,78 +1,104 @@
-use std::collections::HashMap;
+//! [Day 8: Haunted Wasteland](https://adventofcode.com/2023/day/8)
 
-type NodeMap<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+use std::collections::HashMap;
 
-pub fn exec(source: &str) -> (usize, usize) {
-    let (path, map) = parse_instructions(&source);
-    let part_a = calc_steps("AAA", "ZZZ", &map, &path);
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::math::lcm_all;
+use crate::utils::parse::{named_pair, BlockCursor};
 
-    let a_nodes = map
-        .iter()
-        .filter(|(&a, _)| a.ends_with("A"))
-        .map(|(&a, _)| a)
-        .collect::<Vec<_>>();
+/// Each node's name with the nodes to its left and right.
+pub type NodeMap = HashMap<String, (String, String)>;
 
-    let part_b = a_nodes
-        .iter()
-        .map(|&start| calc_steps(start, "Z", &map, &path))
-        .collect::<Vec<_>>();
+/// Counts the steps from `AAA` to `ZZZ`, then for every `..A` node at once.
+pub struct Day8;
 
-    let part_b = lcm_vec(&part_b);
+impl Solution for Day8 {
+    type Input = (Vec<usize>, NodeMap);
 
-    (part_a, part_b)
-}
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        parse_instructions(input)
+    }
 
-fn calc_steps(start: &str, end: &str, nodes: &NodeMap, path: &Vec<usize>) -> usize {
-    let mut path = path.iter().cycle();
-    let mut current_node = start;
-    let mut steps = 0;
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        let (path, map) = input;
+        Ok(calc_steps("AAA", "ZZZ", map, path)?.into())
+    }
 
-    loop {
-        let next_step = path.next().unwrap();
-        let (left, right) = nodes.get(&current_node).unwrap();
-        current_node = match next_step {
-            &0 => *left,
-            &_ => *right,
-        };
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        let (path, map) = input;
 
-        steps += 1;
+        let steps = map
+            .keys()
+            .filter(|node| node.ends_with('A'))
+            .map(|start| calc_steps(start, "Z", map, path))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        if current_node.ends_with(end) {
-            break;
-        }
+        lcm_all(steps)
+            .map(Answer::from)
+            .ok_or_else(|| Error::solve("the number of steps is too large to count"))
     }
-    steps
 }
 
-fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 {
-        return a;
-    } else {
-        return gcd(b, a % b);
+fn calc_steps(start: &str, end: &str, nodes: &NodeMap, path: &[usize]) -> Result<usize, Error> {
+    if path.is_empty() {
+        return Err(Error::solve("the path has no steps"));
     }
-}
 
-fn lcm(a: usize, b: usize) -> usize {
-    return a * b / gcd(a, b);
-}
-
-fn lcm_vec(v: &Vec<usize>) -> usize {
-    return v.iter().fold(1, |acc, &x| lcm(acc, x));
-}
+    // There are only so many pairs of node and place in the path, so once
+    // every one could have come up the walk is going round a loop.
+    let limit = nodes.len() * path.len();
+    let mut current_node = start;
 
-fn parse_instructions(source: &str) -> (Vec<usize>, NodeMap) {
-    let (left, right) = source.split_once("\n\n").unwrap();
+    for (steps, next_step) in path.iter().cycle().take(limit).enumerate() {
+        let (left, right) = nodes
+            .get(current_node)
+            .ok_or_else(|| Error::solve(format!("no node named '{current_node}'")))?;
+        current_node = match next_step {
+            0 => left,
+            _ => right,
+        };
 
-    let nodes = right.split('\n').map(|line| parse_line(line)).collect();
+        if current_node.ends_with(end) {
+            return Ok(steps + 1);
+        }
+    }
 
-    (
-        left.chars().map(|c| if c == 'L' { 0 } else { 1 }).collect(),
-        nodes,
-    )
+    Err(Error::solve(format!(
+        "'{start}' never reaches a node ending in '{end}'"
+    )))
 }
 
-fn parse_line(line: &str) -> (&str, (&str, &str)) {
-    (&line[0..3], (&line[7..10], &line[12..15]))
+fn parse_instructions(input: &PuzzleInput) -> Result<(Vec<usize>, NodeMap), ParseError> {
+    let mut blocks = BlockCursor::new(input);
+    let mut steps = blocks.next_block("the path")?.lines();
+    let nodes = blocks.next_block("the nodes after a blank line")?;
+    blocks.finish()?;
+
+    let steps_line = steps.next_line("the path")?;
+    steps.finish()?;
+    let path = steps_line
+        .chars()
+        .map(|(c, at)| match c {
+            'L' => Ok(0),
+            'R' => Ok(1),
+            _ => Err(at.error("expected 'L' or 'R'")),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let nodes = nodes
+        .lines()
+        .map(|line| {
+            let (node, (left, right)) = named_pair(line)?;
+            Ok((
+                String::from(node.as_str()),
+                (String::from(left.as_str()), String::from(right.as_str())),
+            ))
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    Ok((path, nodes))
 }
 
 #[cfg(test)]
@@ -82,21 +108,65 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_sample_1() {
-        let sample = read_input("8_sample_1");
-        assert_eq!(exec(&sample).0, 2)
+    fn test_crlf_sample() {
+        let sample = read_input("8_sample_2").replace('\n', "\r\n") + "\r\n\r\n";
+        assert_eq!(Day8::exec(&sample).unwrap().0, 6)
     }
 
     #[test]
-    fn test_sample_2() {
-        let sample = read_input("8_sample_2");
-        assert_eq!(exec(&sample).0, 6)
+    fn test_parse_instructions() {
+        let result = parse_instructions(&PuzzleInput::new("LRLR\n\nAAA = (BBB, CCC)")).unwrap();
+        assert_eq!(result.0, vec![0, 1, 0, 1]);
+        assert_eq!(
+            result.1,
+            HashMap::from([(
+                String::from("AAA"),
+                (String::from("BBB"), String::from("CCC"))
+            )])
+        );
     }
 
     #[test]
-    fn test_parse_instructions() {
-        let result = parse_instructions("LRLR\n\nAAA = (BBB, CCC)");
-        assert_eq!(result.0, vec![0, 1, 0, 1]);
-        assert_eq!(result.1, HashMap::from([("AAA", ("BBB", "CCC"))]));
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_instructions(&PuzzleInput::new("LRLR"))
+                .unwrap_err()
+                .line,
+            2
+        );
+        assert_eq!(
+            parse_instructions(&PuzzleInput::new("LR\n\nAAA = (BBB, CCC)\n\nLR"))
+                .unwrap_err()
+                .line,
+            5
+        );
+        assert_eq!(
+            parse_instructions(&PuzzleInput::new("LRX\n\nAAA = (BBB, CCC)"))
+                .unwrap_err()
+                .column,
+            3
+        );
+
+        let error = parse_instructions(&PuzzleInput::new("LR\n\nAAA = (BBB, CCC)\nBBB = BBB, CCC"))
+            .unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (4, 7, "BBB, CCC")
+        );
+    }
+
+    #[test]
+    fn test_unreachable_end() {
+        let error = Day8::exec("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)").unwrap_err();
+        assert_eq!(
+            error,
+            Error::solve("'AAA' never reaches a node ending in 'ZZZ'")
+        );
+    }
+
+    #[test]
+    fn test_missing_node() {
+        let error = Day8::exec("LR\n\nAAA = (BBB, CCC)").unwrap_err();
+        assert_eq!(error, Error::solve("no node named 'BBB'"));
     }
 }
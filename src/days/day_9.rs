@@ -1,37 +1,58 @@
+//! [Day 9: Mirage Maintenance](https://adventofcode.com/2023/day/9)
+
 use std::collections::HashSet;
 
-pub fn exec(source: &str) -> (isize, isize) {
-    let part_a = source
-        .split('\n')
-        .map(|line| predict_next(&parse_line(line, false)))
-        .sum();
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::utils::input::PuzzleInput;
+use crate::utils::parse::Cursor;
+
+/// Extrapolates each history forwards, then backwards.
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Vec<isize>>;
+
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError> {
+        input.lines().map(parse_line).collect()
+    }
 
-    let part_b = source
-        .split('\n')
-        .map(|line| predict_next(&parse_line(line, true)))
-        .sum();
+    fn part_1(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(input
+            .iter()
+            .map(|items| predict_next(items))
+            .sum::<isize>()
+            .into())
+    }
 
-    (part_a, part_b)
+    fn part_2(input: &Self::Input) -> Result<Answer, Error> {
+        Ok(input
+            .iter()
+            .map(|items| predict_next(&items.iter().rev().copied().collect::<Vec<_>>()))
+            .sum::<isize>()
+            .into())
+    }
 }
 
-fn parse_line(line: &str, reverse: bool) -> Vec<isize> {
-    let result = line.split_whitespace().map(|p| p.parse().unwrap());
+fn parse_line<'a>(line: impl Into<Cursor<'a>>) -> Result<Vec<isize>, ParseError> {
+    let line = line.into();
+    let result = line.numbers()?;
 
-    if reverse {
-        result.rev().collect()
-    } else {
-        result.collect()
+    if result.len() < 2 {
+        return Err(line.error("expected at least two numbers"));
     }
+
+    Ok(result)
 }
 
-fn predict_next(items: &Vec<isize>) -> isize {
+fn predict_next(items: &[isize]) -> isize {
     let last = *items.last().unwrap();
     let diffs = items
         .windows(2)
         .map(|i| i[1] - i[0])
         .collect::<Vec<isize>>();
 
-    let uniques: HashSet<isize> = HashSet::from_iter(diffs.iter().map(|&a| a));
+    let uniques: HashSet<isize> = HashSet::from_iter(diffs.iter().copied());
 
     let diff = *diffs.last().unwrap();
 
@@ -54,48 +75,50 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_input;
-
     use super::*;
 
     #[test]
     fn test_parse_line() {
-        assert_eq!(parse_line("1 2 3", false), vec![1, 2, 3]);
+        assert_eq!(parse_line("1 2 3"), Ok(vec![1, 2, 3]));
     }
 
     #[test]
-    fn test_sample() {
-        let sample = read_input("9_sample_1");
-        assert_eq!(exec(&sample).0, 114);
+    fn test_parse_line_errors() {
+        assert_eq!(parse_line("1 2 x").unwrap_err().column, 5);
+        assert_eq!(parse_line("1").unwrap_err().text, "1");
+        assert_eq!(
+            Day9::parse(&PuzzleInput::new("1 2\n3")).unwrap_err().line,
+            2
+        );
     }
 
     #[test]
     fn test_pairwise() {
-        let items = vec![1, 2, 3];
+        let items = [1, 2, 3];
         let result = pairwise(items.iter()).collect::<Vec<(&i32, &i32)>>();
         assert_eq!(result, vec![(&1, &2), (&2, &3)])
     }
 
     #[test]
     fn test_predict_next_1() {
-        let items = vec![1, 2, 3, 4];
+        let items = [1, 2, 3, 4];
         assert_eq!(predict_next(&items), 5);
     }
 
     #[test]
     fn test_predict_next_2() {
-        let items = vec![0, 3, 6, 9, 12, 15];
+        let items = [0, 3, 6, 9, 12, 15];
         assert_eq!(predict_next(&items), 18);
     }
 
     #[test]
     fn test_predict_next_3() {
-        let items = vec![1, 3, 6, 10, 15, 21];
+        let items = [1, 3, 6, 10, 15, 21];
         assert_eq!(predict_next(&items), 28);
     }
     #[test]
     fn test_predict_next_4() {
-        let items = vec![10, 13, 16, 21, 30, 45];
+        let items = [10, 13, 16, 21, 30, 45];
         assert_eq!(predict_next(&items), 68);
     }
 }
@@ -1,3 +1,11 @@
+//! The solution for each day, and a registry to run them by number.
+
+use std::any::Any;
+
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Part, Solution};
+use crate::utils::input::PuzzleInput;
+
 pub mod day_1;
 pub mod day_10;
 pub mod day_11;
@@ -11,3 +19,171 @@ pub mod day_6;
 pub mod day_7;
 pub mod day_8;
 pub mod day_9;
+
+/// A registered day, with its solution erased down to plain function pointers.
+///
+/// The parsed input is boxed so that it can be shared between the two parts
+/// without the registry knowing each day's input type.
+pub struct Day {
+    /// The day of December the puzzle is for.
+    pub number: u8,
+    /// See [`Solution::reset`].
+    pub reset: fn(),
+    parse: fn(&PuzzleInput) -> Result<Box<dyn Any>, ParseError>,
+    part_1: fn(&dyn Any) -> Result<Answer, Error>,
+    part_2: fn(&dyn Any) -> Result<Answer, Error>,
+}
+
+impl Day {
+    /// Parse the input into the day's model, boxed for [`Day::solve`].
+    pub fn parse(&self, input: &PuzzleInput) -> Result<Box<dyn Any>, ParseError> {
+        (self.parse)(input).map_err(|error| error.in_day(self.number))
+    }
+
+    /// Answer one part from an input returned by [`Day::parse`].
+    ///
+    /// # Panics
+    ///
+    /// If `input` was parsed by a different day.
+    pub fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer, Error> {
+        match part {
+            Part::One => (self.part_1)(input),
+            Part::Two => (self.part_2)(input),
+        }
+        .map_err(|error| error.in_day(self.number))
+    }
+}
+
+const fn day<S: Solution>(number: u8) -> Day
+where
+    S::Input: 'static,
+{
+    Day {
+        number,
+        parse: parse::<S>,
+        reset: S::reset,
+        part_1: part_1::<S>,
+        part_2: part_2::<S>,
+    }
+}
+
+fn parse<S: Solution>(input: &PuzzleInput) -> Result<Box<dyn Any>, ParseError>
+where
+    S::Input: 'static,
+{
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn part_1<S: Solution>(input: &dyn Any) -> Result<Answer, Error>
+where
+    S::Input: 'static,
+{
+    S::part_1(downcast::<S>(input))
+}
+
+fn part_2<S: Solution>(input: &dyn Any) -> Result<Answer, Error>
+where
+    S::Input: 'static,
+{
+    S::part_2(downcast::<S>(input))
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input
+where
+    S::Input: 'static,
+{
+    input
+        .downcast_ref()
+        .expect("Input was parsed by a different day")
+}
+
+/// Every solved day, in order.
+pub const DAYS: &[Day] = &[
+    day::<day_1::Day1>(1),
+    day::<day_2::Day2>(2),
+    day::<day_3::Day3>(3),
+    day::<day_4::Day4>(4),
+    day::<day_5::Day5>(5),
+    day::<day_6::Day6>(6),
+    day::<day_7::Day7>(7),
+    day::<day_8::Day8>(8),
+    day::<day_9::Day9>(9),
+    day::<day_10::Day10>(10),
+    day::<day_11::Day11>(11),
+    day::<day_12::Day12>(12),
+    day::<day_13::Day13>(13),
+];
+
+/// Look up a day in [`DAYS`] by its number.
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::read_input;
+
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        let numbers = DAYS.iter().map(|day| day.number).collect::<Vec<_>>();
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        sorted.dedup();
+
+        assert_eq!(numbers, sorted);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(7).map(|day| day.number), Some(7));
+        assert!(find(26).is_none());
+    }
+
+    #[test]
+    fn test_solve_single_part() {
+        let day = find(11).unwrap();
+        let input = day
+            .parse(&PuzzleInput::new(&read_input("11_sample_1")))
+            .unwrap();
+
+        assert_eq!(day.solve(input.as_ref(), Part::One), Ok(374.into()));
+        assert_eq!(day.solve(input.as_ref(), Part::Two), Ok(82000210.into()));
+    }
+
+    #[test]
+    fn test_errors_name_the_day() {
+        let day = find(11).unwrap();
+        let error = day.parse(&PuzzleInput::new("..#\n.x.")).err().unwrap();
+
+        assert_eq!(error.day, Some(11));
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn test_errors_count_leading_blank_lines() {
+        let day = find(9).unwrap();
+        let error = day.parse(&PuzzleInput::new("\n\n1 2\n3")).err().unwrap();
+
+        assert_eq!((error.line, error.text.as_str()), (4, "3"));
+    }
+
+    /// Solve one part of a sample listed in `data/samples.toml`.
+    fn check_sample(number: u8, sample: &str, part: Part, expected: &str) {
+        let day = find(number).unwrap_or_else(|| panic!("Day {number} has no solution"));
+        let input = day.parse(&PuzzleInput::new(&read_input(sample))).unwrap();
+
+        assert_eq!(
+            day.solve(input.as_ref(), part).unwrap().to_string(),
+            expected
+        );
+    }
+
+    /// One test per part of each entry in `data/samples.toml`, from build.rs.
+    mod samples {
+        use super::*;
+
+        include!(concat!(env!("OUT_DIR"), "/sample_tests.rs"));
+    }
+}
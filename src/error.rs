@@ -0,0 +1,153 @@
+//! Errors from parsing inputs and solving puzzles.
+
+use std::fmt::{Display, Formatter};
+
+/// A problem with a puzzle input, located by 1-based line and column.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParseError {
+    /// The day whose input it is, once known.
+    pub day: Option<u8>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    /// What was expected instead.
+    pub message: String,
+}
+
+impl ParseError {
+    /// Report `fragment`, which must be a slice of `line`, as the offending
+    /// text. The line number is 1 until a caller shifts it into place.
+    pub fn at(line: &str, fragment: &str, message: impl Into<String>) -> ParseError {
+        let offset = (fragment.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+
+        ParseError {
+            day: None,
+            line: 1,
+            column: line
+                .get(..offset)
+                .map_or(0, |before| before.chars().count())
+                + 1,
+            text: String::from(fragment),
+            message: message.into(),
+        }
+    }
+
+    /// Move an error found in a line or block down by `lines`.
+    pub fn shifted(mut self, lines: usize) -> ParseError {
+        self.line += lines;
+        self
+    }
+
+    /// Name the day the error is from.
+    pub fn in_day(mut self, day: u8) -> ParseError {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "Day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {} (found '{}')",
+            self.line,
+            self.column,
+            self.message,
+            self.text.escape_debug()
+        )
+    }
+}
+
+/// Why a day couldn't produce an answer.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Error {
+    /// The input couldn't be read.
+    Parse(ParseError),
+    /// The input was read, but has no answer.
+    Solve {
+        /// The day it's from, once known.
+        day: Option<u8>,
+        /// Why there's no answer.
+        message: String,
+    },
+}
+
+impl Error {
+    /// A [`Error::Solve`] not yet tied to a day.
+    pub fn solve(message: impl Into<String>) -> Error {
+        Error::Solve {
+            day: None,
+            message: message.into(),
+        }
+    }
+
+    /// Name the day the error is from.
+    pub fn in_day(self, day: u8) -> Error {
+        match self {
+            Error::Parse(error) => Error::Parse(error.in_day(day)),
+            Error::Solve { message, .. } => Error::Solve {
+                day: Some(day),
+                message,
+            },
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::Solve { day: None, message } => write!(f, "{message}"),
+            Error::Solve {
+                day: Some(day),
+                message,
+            } => write!(f, "Day {day}: {message}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_finds_column() {
+        let line = "Game 1: 3 purple";
+        let error = ParseError::at(line, &line[10..], "unknown colour");
+
+        assert_eq!(error.line, 1);
+        assert_eq!(error.column, 11);
+        assert_eq!(error.text, "purple");
+    }
+
+    #[test]
+    fn test_display() {
+        let line = "12x";
+        let error = ParseError::at(line, &line[2..], "not a digit").shifted(4);
+
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 3: not a digit (found 'x')"
+        );
+        assert_eq!(
+            Error::from(error).in_day(3).to_string(),
+            "Day 3, line 5, column 3: not a digit (found 'x')"
+        );
+        assert_eq!(
+            Error::solve("no start tile").in_day(10).to_string(),
+            "Day 10: no start tile"
+        );
+    }
+}
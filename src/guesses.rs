@@ -0,0 +1,321 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use aoc_2023::solution::Part;
+
+pub const GUESSES_PATH: &str = "data/guesses.log";
+
+/// Why the puzzle site turned down an answer.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Rejection {
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Wrong => write!(f, "wrong"),
+            Rejection::TooHigh => write!(f, "too_high"),
+            Rejection::TooLow => write!(f, "too_low"),
+        }
+    }
+}
+
+impl FromStr for Rejection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wrong" => Ok(Rejection::Wrong),
+            "too_high" => Ok(Rejection::TooHigh),
+            "too_low" => Ok(Rejection::TooLow),
+            _ => Err(format!("'{s}' is not a rejection")),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+struct Guess {
+    day: u8,
+    part: Part,
+    rejection: Rejection,
+    answer: String,
+}
+
+/// Answers the puzzle site has rejected, one per line as
+/// `<day> <part> <wrong|too_high|too_low> <answer>`, so that they are never
+/// submitted twice. A `wait <unix seconds>` line records when the site will
+/// next accept an answer after asking us to slow down.
+#[derive(PartialEq, Debug, Default)]
+pub struct GuessLog {
+    guesses: Vec<Guess>,
+    wait_until: Option<u64>,
+}
+
+impl GuessLog {
+    /// Load the log, treating a missing file as an empty log.
+    pub fn load(path: &Path) -> Result<GuessLog, String> {
+        match fs::read_to_string(path) {
+            Ok(source) => GuessLog::parse(&source),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(GuessLog::default()),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    pub fn parse(source: &str) -> Result<GuessLog, String> {
+        let mut guesses = vec![];
+        let mut wait_until = None;
+
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(until) = line.strip_prefix("wait ") {
+                wait_until = Some(
+                    until
+                        .parse()
+                        .map_err(|_| format!("line {line_number}: '{until}' is not a time"))?,
+                );
+                continue;
+            }
+
+            let fields = line.splitn(4, ' ').collect::<Vec<_>>();
+            let [day, part, rejection, answer] = fields[..] else {
+                return Err(format!(
+                    "line {line_number}: expected '<day> <part> <rejection> <answer>'"
+                ));
+            };
+
+            guesses.push(Guess {
+                day: day
+                    .parse()
+                    .map_err(|_| format!("line {line_number}: '{day}' is not a day"))?,
+                part: part
+                    .parse()
+                    .map_err(|error| format!("line {line_number}: {error}"))?,
+                rejection: rejection
+                    .parse()
+                    .map_err(|error| format!("line {line_number}: {error}"))?,
+                answer: String::from(answer),
+            });
+        }
+
+        Ok(GuessLog {
+            guesses,
+            wait_until,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, rejection: Rejection, answer: &str) {
+        self.guesses.push(Guess {
+            day,
+            part,
+            rejection,
+            answer: String::from(answer),
+        });
+    }
+
+    /// Refuse answers until `until`, in seconds since the Unix epoch.
+    pub fn wait_until(&mut self, until: u64) {
+        self.wait_until = Some(until);
+    }
+
+    /// The seconds left to wait at `now`, if the site asked us to wait.
+    pub fn waiting(&self, now: u64) -> Option<u64> {
+        self.wait_until
+            .filter(|&until| until > now)
+            .map(|until| until - now)
+    }
+
+    /// Explain why `answer` can't be right, if an earlier rejection rules it
+    /// out: it was already rejected, or it lies beyond a too high or too low
+    /// bound.
+    pub fn rule_out(&self, day: u8, part: Part, answer: &str) -> Option<String> {
+        let guesses = self
+            .guesses
+            .iter()
+            .filter(|guess| guess.day == day && guess.part == part);
+        let value = answer.parse::<i128>().ok();
+
+        for guess in guesses {
+            if guess.answer == answer {
+                return Some(format!(
+                    "{answer} was already rejected as {}",
+                    guess.rejection
+                ));
+            }
+
+            let (Some(value), Ok(bound)) = (value, guess.answer.parse::<i128>()) else {
+                continue;
+            };
+            match guess.rejection {
+                Rejection::TooHigh if value > bound => {
+                    return Some(format!("{answer} is above {bound}, which was too high"));
+                }
+                Rejection::TooLow if value < bound => {
+                    return Some(format!("{answer} is below {bound}, which was too low"));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+impl Display for GuessLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for guess in &self.guesses {
+            writeln!(
+                f,
+                "{} {} {} {}",
+                guess.day, guess.part, guess.rejection, guess.answer
+            )?;
+        }
+        if let Some(until) = self.wait_until {
+            writeln!(f, "wait {until}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The seconds in a wait as the site describes it, such as `1m 20s` or
+/// `one minute`, or a minute if the description can't be read.
+pub fn wait_seconds(left: &str) -> u64 {
+    read_wait(left).filter(|&seconds| seconds > 0).unwrap_or(60)
+}
+
+fn read_wait(left: &str) -> Option<u64> {
+    let mut seconds = 0;
+    let mut count = None;
+
+    for word in left.split_whitespace() {
+        let value = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let unit = &word[value.len()..];
+
+        if !value.is_empty() {
+            let value = value.parse::<u64>().ok()?;
+            match unit {
+                "" => count = Some(value),
+                _ => seconds += value * unit_seconds(unit)?,
+            }
+        } else if let Some(value) = number_word(word) {
+            count = Some(value);
+        } else {
+            seconds += count.take()? * unit_seconds(word)?;
+        }
+    }
+
+    count.is_none().then_some(seconds)
+}
+
+fn unit_seconds(unit: &str) -> Option<u64> {
+    match unit {
+        "h" | "hour" | "hours" => Some(3600),
+        "m" | "minute" | "minutes" => Some(60),
+        "s" | "second" | "seconds" => Some(1),
+        _ => None,
+    }
+}
+
+fn number_word(word: &str) -> Option<u64> {
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    match word {
+        "a" | "an" => Some(1),
+        _ => (1..)
+            .zip(words)
+            .find(|&(_, name)| name == word)
+            .map(|(value, _)| value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "5 1 too_high 900
+5 1 too_low 100
+5 2 wrong 42
+";
+
+    #[test]
+    fn test_round_trip() {
+        let log = GuessLog::parse(SAMPLE).unwrap();
+        assert_eq!(log.to_string(), SAMPLE);
+
+        let mut log = GuessLog::default();
+        log.insert(1, Part::Two, Rejection::Wrong, "two words");
+        assert_eq!(GuessLog::parse(&log.to_string()).unwrap(), log);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(GuessLog::parse("5 1 too_high").is_err());
+        assert!(GuessLog::parse("x 1 wrong 4").is_err());
+        assert!(GuessLog::parse("5 3 wrong 4").is_err());
+        assert!(GuessLog::parse("5 1 close 4").is_err());
+    }
+
+    #[test]
+    fn test_wait() {
+        let mut log = GuessLog::parse(SAMPLE).unwrap();
+        assert_eq!(log.waiting(1_000), None);
+
+        log.wait_until(1_080);
+        let log = GuessLog::parse(&log.to_string()).unwrap();
+        assert_eq!(log.waiting(1_000), Some(80));
+        assert_eq!(log.waiting(1_080), None);
+        assert!(GuessLog::parse("wait soon").is_err());
+    }
+
+    #[test]
+    fn test_rejection_with_wait() {
+        let mut log = GuessLog::default();
+        log.insert(5, Part::One, Rejection::TooHigh, "900");
+        log.wait_until(1_000 + wait_seconds("one minute"));
+
+        let log = GuessLog::parse(&log.to_string()).unwrap();
+        assert!(log.rule_out(5, Part::One, "900").is_some());
+        assert_eq!(log.waiting(1_000), Some(60));
+    }
+
+    #[test]
+    fn test_wait_seconds() {
+        assert_eq!(wait_seconds("1m 20s"), 80);
+        assert_eq!(wait_seconds("41s"), 41);
+        assert_eq!(wait_seconds("1h 2m 3s"), 3723);
+        assert_eq!(wait_seconds("a little while"), 60);
+        assert_eq!(wait_seconds("20é"), 60);
+        assert_eq!(wait_seconds("one minute"), 60);
+        assert_eq!(wait_seconds("5 minutes"), 300);
+        assert_eq!(wait_seconds("30 seconds"), 30);
+        assert_eq!(wait_seconds("ten minutes"), 600);
+        assert_eq!(wait_seconds("5"), 60);
+    }
+
+    #[test]
+    fn test_rule_out() {
+        let log = GuessLog::parse(SAMPLE).unwrap();
+
+        assert!(log.rule_out(5, Part::One, "900").is_some());
+        assert!(log.rule_out(5, Part::One, "901").is_some());
+        assert!(log.rule_out(5, Part::One, "99").is_some());
+        assert_eq!(log.rule_out(5, Part::One, "500"), None);
+        assert!(log.rule_out(5, Part::Two, "42").is_some());
+        assert_eq!(log.rule_out(5, Part::Two, "43"), None);
+        assert_eq!(log.rule_out(6, Part::One, "900"), None);
+    }
+}
@@ -0,0 +1,27 @@
+//! Solutions to [Advent of Code 2023](https://adventofcode.com/2023).
+//!
+//! Each day is a [`Solution`](solution::Solution) in [`days`], parsing its
+//! input into a model that both parts are answered from. The days are also
+//! registered in [`days::DAYS`], so they can be run by number without knowing
+//! their input types:
+//!
+//! ```
+//! use aoc_2023::days;
+//! use aoc_2023::solution::Part;
+//! use aoc_2023::utils::input::PuzzleInput;
+//!
+//! let day = days::find(9).unwrap();
+//! let input = day.parse(&PuzzleInput::new("0 3 6 9 12 15\n1 3 6 10 15 21")).unwrap();
+//!
+//! assert_eq!(day.solve(input.as_ref(), Part::One).unwrap(), 46);
+//! ```
+//!
+//! The command line runner in `main.rs` is a thin layer over this crate that
+//! adds the file handling, the puzzle site client and the reports.
+
+#![warn(missing_docs)]
+
+pub mod days;
+pub mod error;
+pub mod solution;
+pub mod utils;
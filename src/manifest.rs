@@ -0,0 +1,141 @@
+//! The sample manifest, `data/samples.toml`. It's read both by build.rs,
+//! which turns it into tests, and by `watch`.
+
+pub const MANIFEST_PATH: &str = "data/samples.toml";
+
+/// The expected answer for one part of a sample.
+#[derive(PartialEq, Debug)]
+pub struct Entry {
+    /// The sample's file name in `data/`.
+    pub sample: String,
+    pub day: u8,
+    pub part: u8,
+    pub expected: String,
+}
+
+/// Read the manifest: a table per sample file in `data/`, naming the day
+/// it's for and the expected answer of each part it has one for.
+///
+/// ```toml
+/// [8_sample_2]
+/// day = 8
+/// part_1 = "6"
+/// ```
+pub fn parse(source: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = vec![];
+    let mut table: Option<Table> = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            if let Some(table) = table.take() {
+                entries.extend(table.into_entries()?);
+            }
+            table = Some(Table {
+                sample: String::from(name.trim()),
+                day: None,
+                parts: vec![],
+            });
+            continue;
+        }
+
+        let table = table.as_mut().ok_or(format!(
+            "line {line_number}: entry outside of a [sample] table"
+        ))?;
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(format!("line {line_number}: expected 'key = value'"))?;
+        let (key, value) = (key.trim(), value.trim());
+
+        match key {
+            "day" => {
+                table.day = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("line {line_number}: '{value}' is not a day"))?,
+                )
+            }
+            "part_1" | "part_2" => {
+                let expected = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .ok_or(format!("line {line_number}: answers must be quoted"))?;
+                let part = if key == "part_1" { 1 } else { 2 };
+                table.parts.push((part, String::from(expected)));
+            }
+            _ => return Err(format!("line {line_number}: unknown key '{key}'")),
+        }
+    }
+
+    if let Some(table) = table {
+        entries.extend(table.into_entries()?);
+    }
+    Ok(entries)
+}
+
+/// One `[sample]` table as it's read.
+struct Table {
+    sample: String,
+    day: Option<u8>,
+    parts: Vec<(u8, String)>,
+}
+
+impl Table {
+    fn into_entries(self) -> Result<Vec<Entry>, String> {
+        let day = self.day.ok_or(format!("[{}] has no day", self.sample))?;
+
+        Ok(self
+            .parts
+            .into_iter()
+            .map(|(part, expected)| Entry {
+                sample: self.sample.clone(),
+                day,
+                part,
+                expected,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let entries =
+            parse("# Samples\n[8_sample_2]\nday = 8\npart_1 = \"6\"\npart_2 = \"6\"\n").unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                Entry {
+                    sample: String::from("8_sample_2"),
+                    day: 8,
+                    part: 1,
+                    expected: String::from("6"),
+                },
+                Entry {
+                    sample: String::from("8_sample_2"),
+                    day: 8,
+                    part: 2,
+                    expected: String::from("6"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("day = 8").is_err());
+        assert!(parse("[8_sample_1]\npart_1 = \"6\"").is_err());
+        assert!(parse("[8_sample_1]\nday = 8\npart_1 = 6").is_err());
+        assert!(parse("[8_sample_1]\nday = 8\npart_3 = \"6\"").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Once};
+use std::thread;
+
+thread_local! {
+    /// Whether panics on this thread are left for the caller to report.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Apply `f` to every item on up to `jobs` threads, handing each result to
+/// `report` in the original order as soon as it and everything before it
+/// are done. With one job everything runs in order on the calling thread.
+///
+/// A panic in `f` is caught and passed on as the panic message. Worker
+/// threads are quiet about panics if the calling thread is, see
+/// [`with_quiet_panics`].
+pub fn map_ordered<T, R, F>(
+    items: &[T],
+    jobs: usize,
+    f: F,
+    mut report: impl FnMut(&T, Result<R, String>),
+) where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let call = |item: &T| panic::catch_unwind(AssertUnwindSafe(|| f(item))).map_err(panic_message);
+
+    if jobs <= 1 || items.len() <= 1 {
+        for item in items {
+            report(item, call(item));
+        }
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let quiet = QUIET.get();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let (next, call) = (&next, &call);
+
+            scope.spawn(move || {
+                QUIET.set(quiet);
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, call(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut reported = 0;
+
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&reported) {
+                report(&items[reported], result);
+                reported += 1;
+            }
+        }
+    });
+}
+
+/// Run `f` without the panic hook printing panics from this thread or from
+/// the [`map_ordered`] workers it starts, since callers report them
+/// themselves. Panics on every other thread are printed as usual.
+pub fn with_quiet_panics<R>(f: impl FnOnce() -> R) -> R {
+    /// Puts the flag back even if `f` panics.
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            QUIET.set(self.0);
+        }
+    }
+
+    install_hook();
+    let _restore = Restore(QUIET.replace(true));
+    f()
+}
+
+/// Wrap the panic hook, once, so that it skips quiet threads.
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                hook(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        String::from(*message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn collect(items: &[u64], jobs: usize) -> Vec<(u64, Result<u64, String>)> {
+        let mut results = vec![];
+        map_ordered(
+            items,
+            jobs,
+            |&item| {
+                // Finish later items first to check the reordering.
+                thread::sleep(Duration::from_millis(10 - item));
+                item * 2
+            },
+            |&item, result| results.push((item, result)),
+        );
+        results
+    }
+
+    #[test]
+    fn test_results_keep_item_order() {
+        let expected = (1..=8).map(|item| (item, Ok(item * 2))).collect::<Vec<_>>();
+        let items = (1..=8).collect::<Vec<_>>();
+
+        assert_eq!(collect(&items, 1), expected);
+        assert_eq!(collect(&items, 4), expected);
+        assert_eq!(collect(&items, 20), expected);
+    }
+
+    #[test]
+    fn test_quiet_is_scoped_to_the_caller_and_its_workers() {
+        let quiet = |jobs| {
+            let mut results = vec![];
+            map_ordered(
+                &[1, 2, 3],
+                jobs,
+                |_| QUIET.get(),
+                |_, result| results.push(result.unwrap()),
+            );
+            results
+        };
+
+        assert_eq!(with_quiet_panics(|| quiet(2)), vec![true; 3]);
+        assert_eq!(with_quiet_panics(|| quiet(1)), vec![true; 3]);
+        assert_eq!(quiet(2), vec![false; 3]);
+        assert!(!QUIET.get());
+        assert!(!with_quiet_panics(|| thread::spawn(|| QUIET.get())
+            .join()
+            .unwrap()));
+    }
+
+    #[test]
+    fn test_panics_are_captured() {
+        let mut results = vec![];
+        with_quiet_panics(|| {
+            map_ordered(
+                &[1, 2, 3],
+                2,
+                |&item| {
+                    if item == 2 {
+                        panic!("Shouldn't be possible");
+                    }
+                    item
+                },
+                |_, result| results.push(result),
+            )
+        });
+
+        assert_eq!(
+            results,
+            vec![Ok(1), Err(String::from("Shouldn't be possible")), Ok(3)]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_2023::utils::{self, DATA_DIR};
+
+use crate::answers::ANSWERS_PATH;
+
+/// A set of real inputs with their own confirmed answers, such as one per
+/// account. The default profile is `data/` itself, and a named one is a
+/// directory inside it, `data/<name>/<DAY>` with `data/<name>/answers.toml`.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Profile {
+    name: Option<String>,
+}
+
+impl Profile {
+    /// A named profile. Names start with a letter so that they can't be
+    /// confused with the inputs next to them.
+    pub fn named(name: &str) -> Result<Profile, String> {
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !valid {
+            return Err(format!(
+                "'{name}' is not a profile name, expected a letter followed by \
+                 letters, digits, '-' or '_'"
+            ));
+        }
+
+        Ok(Profile {
+            name: Some(String::from(name)),
+        })
+    }
+
+    /// The default profile followed by every named one in `data/`, by name.
+    pub fn all() -> Result<Vec<Profile>, String> {
+        let mut names = fs::read_dir(DATA_DIR)
+            .map_err(|error| error.to_string())?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect::<Vec<_>>();
+        names.sort();
+
+        Ok([Profile::default()]
+            .into_iter()
+            .chain(names.iter().filter_map(|name| Profile::named(name).ok()))
+            .collect())
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.name.is_none()
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        match &self.name {
+            None => utils::input_path(&day.to_string()),
+            Some(name) => Path::new(DATA_DIR).join(name).join(day.to_string()),
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        match &self.name {
+            None => PathBuf::from(ANSWERS_PATH),
+            Some(name) => Path::new(DATA_DIR).join(name).join("answers.toml"),
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name.as_deref().unwrap_or("default"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let default = Profile::default();
+        assert_eq!(default.input_path(5), Path::new("data/5"));
+        assert_eq!(default.answers_path(), Path::new("data/answers.toml"));
+
+        let alice = Profile::named("alice").unwrap();
+        assert_eq!(alice.input_path(5), Path::new("data/alice/5"));
+        assert_eq!(alice.answers_path(), Path::new("data/alice/answers.toml"));
+        assert_eq!(alice.to_string(), "alice");
+    }
+
+    #[test]
+    fn test_names() {
+        assert!(Profile::named("work-2").is_ok());
+        assert!(Profile::named("").is_err());
+        assert!(Profile::named("5").is_err());
+        assert!(Profile::named("../alice").is_err());
+        assert!(Profile::named("a/b").is_err());
+    }
+}
@@ -0,0 +1,341 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_2023::solution::{Answer, Part};
+
+use crate::alloc::Allocations;
+use crate::answers::Verdict;
+
+/// How `run` prints its results.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!(
+                "'{s}' is not a format, expected text, json, csv or tsv"
+            )),
+        }
+    }
+}
+
+/// One solved part, with everything needed to compare runs.
+#[derive(PartialEq, Debug)]
+pub struct Row {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub input: String,
+    /// The profile the input belongs to, when more than the default profile
+    /// is involved or the input is a named profile's.
+    pub profile: Option<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// What parsing and solving allocated, when the `count-allocations`
+    /// feature is on.
+    pub allocations: Option<(Allocations, Allocations)>,
+    /// How the answer compares to `data/answers.toml`, or `None` when the
+    /// input isn't the day's real input and so has no known answer.
+    pub known: Option<Verdict>,
+}
+
+impl Row {
+    /// Values for the allocation columns, in order.
+    fn allocation_fields(&self) -> Option<[u64; 6]> {
+        self.allocations.map(|(parse, solve)| {
+            [
+                parse.count,
+                parse.bytes,
+                parse.peak,
+                solve.count,
+                solve.bytes,
+                solve.peak,
+            ]
+        })
+    }
+
+    fn known(&self) -> Option<&'static str> {
+        self.known.as_ref().map(|verdict| match verdict {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Missing => "missing",
+        })
+    }
+}
+
+const COLUMNS: [&str; 8] = [
+    "day", "part", "answer", "input", "profile", "parse_ns", "solve_ns", "known",
+];
+
+/// Added after the others when allocations were counted.
+const ALLOCATION_COLUMNS: [&str; 6] = [
+    "parse_allocs",
+    "parse_bytes",
+    "parse_peak",
+    "solve_allocs",
+    "solve_bytes",
+    "solve_peak",
+];
+
+pub fn render(format: Format, rows: &[Row]) -> String {
+    match format {
+        Format::Text => text(rows),
+        Format::Json => json(rows),
+        Format::Csv => delimited(rows, ',', csv_field),
+        Format::Tsv => delimited(rows, '\t', tsv_field),
+    }
+}
+
+/// The original `Day N, Part M: answer` lines, with a blank line after each day.
+fn text(rows: &[Row]) -> String {
+    let mut out = String::new();
+
+    for (index, row) in rows.iter().enumerate() {
+        write!(out, "Day {}, Part {}", row.day, row.part).unwrap();
+        if let Some(profile) = &row.profile {
+            write!(out, " ({profile})").unwrap();
+        }
+        writeln!(out, ": {}", row.answer).unwrap();
+        if rows.get(index + 1).is_none_or(|next| next.day != row.day) {
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+fn json(rows: &[Row]) -> String {
+    let mut out = String::from("[");
+
+    for (index, row) in rows.iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        write!(
+            out,
+            "\n  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"input\": {}, \
+             \"profile\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"known\": {}",
+            row.day,
+            row.part,
+            json_string(&row.answer.to_string()),
+            json_string(&row.input),
+            row.profile
+                .as_deref()
+                .map_or(String::from("null"), json_string),
+            row.parse_time.as_nanos(),
+            row.solve_time.as_nanos(),
+            row.known().map_or(String::from("null"), json_string),
+        )
+        .unwrap();
+        if let Some(values) = row.allocation_fields() {
+            for (column, value) in ALLOCATION_COLUMNS.iter().zip(values) {
+                write!(out, ", \"{column}\": {value}").unwrap();
+            }
+        }
+        out.push('}');
+    }
+
+    if !rows.is_empty() {
+        out.push('\n');
+    }
+    out.push_str("]\n");
+    out
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+fn delimited(rows: &[Row], separator: char, field: fn(&str) -> String) -> String {
+    let mut out = String::new();
+    let mut line = |fields: &[String]| {
+        let fields = fields.iter().map(|value| field(value)).collect::<Vec<_>>();
+        out.push_str(&fields.join(&separator.to_string()));
+        out.push('\n');
+    };
+
+    let counted = rows.iter().any(|row| row.allocations.is_some());
+    let mut columns = COLUMNS.to_vec();
+    if counted {
+        columns.extend(ALLOCATION_COLUMNS);
+    }
+
+    line(&columns.into_iter().map(String::from).collect::<Vec<_>>());
+    for row in rows {
+        let mut fields = vec![
+            row.day.to_string(),
+            row.part.to_string(),
+            row.answer.to_string(),
+            row.input.clone(),
+            row.profile.clone().unwrap_or_default(),
+            row.parse_time.as_nanos().to_string(),
+            row.solve_time.as_nanos().to_string(),
+            row.known().unwrap_or_default().to_string(),
+        ];
+        if counted {
+            fields.extend(match row.allocation_fields() {
+                Some(values) => values.map(|value| value.to_string()),
+                None => Default::default(),
+            });
+        }
+        line(&fields);
+    }
+
+    out
+}
+
+/// Quote a field only when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
+
+/// TSV has no quoting, so escape the characters that would break a row.
+fn tsv_field(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                day: 3,
+                part: Part::One,
+                answer: 4361.into(),
+                input: String::from("data/3"),
+                profile: None,
+                parse_time: Duration::from_micros(12),
+                solve_time: Duration::from_nanos(900),
+                allocations: None,
+                known: Some(Verdict::Pass),
+            },
+            Row {
+                day: 3,
+                part: Part::Two,
+                answer: "a,\"b\"".into(),
+                input: String::from("my\tinput"),
+                profile: Some(String::from("alice")),
+                parse_time: Duration::from_micros(12),
+                solve_time: Duration::from_nanos(5),
+                allocations: None,
+                known: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("tsv".parse(), Ok(Format::Tsv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            render(Format::Text, &rows()),
+            "Day 3, Part 1: 4361\nDay 3, Part 2 (alice): a,\"b\"\n\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            render(Format::Json, &rows()),
+            "[
+  {\"day\": 3, \"part\": 1, \"answer\": \"4361\", \"input\": \"data/3\", \"profile\": null, \"parse_ns\": 12000, \"solve_ns\": 900, \"known\": \"pass\"},
+  {\"day\": 3, \"part\": 2, \"answer\": \"a,\\\"b\\\"\", \"input\": \"my\\tinput\", \"profile\": \"alice\", \"parse_ns\": 12000, \"solve_ns\": 5, \"known\": null}
+]
+"
+        );
+        assert_eq!(render(Format::Json, &[]), "[]\n");
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            render(Format::Csv, &rows()),
+            "day,part,answer,input,profile,parse_ns,solve_ns,known
+3,1,4361,data/3,,12000,900,pass
+3,2,\"a,\"\"b\"\"\",my\tinput,alice,12000,5,
+"
+        );
+    }
+
+    #[test]
+    fn test_allocations() {
+        let allocations = |count| Allocations {
+            count,
+            bytes: count * 8,
+            peak: count * 4,
+        };
+        let mut rows = rows();
+        rows.truncate(1);
+        rows[0].allocations = Some((allocations(2), allocations(10)));
+
+        assert_eq!(
+            render(Format::Csv, &rows),
+            "day,part,answer,input,profile,parse_ns,solve_ns,known,\
+             parse_allocs,parse_bytes,parse_peak,solve_allocs,solve_bytes,solve_peak
+3,1,4361,data/3,,12000,900,pass,2,16,8,10,80,40
+"
+        );
+        assert_eq!(
+            render(Format::Json, &rows),
+            "[
+  {\"day\": 3, \"part\": 1, \"answer\": \"4361\", \"input\": \"data/3\", \"profile\": null, \"parse_ns\": 12000, \"solve_ns\": 900, \"known\": \"pass\", \
+\"parse_allocs\": 2, \"parse_bytes\": 16, \"parse_peak\": 8, \"solve_allocs\": 10, \"solve_bytes\": 80, \"solve_peak\": 40}
+]
+"
+        );
+    }
+
+    #[test]
+    fn test_tsv() {
+        assert_eq!(
+            render(Format::Tsv, &rows()),
+            "day\tpart\tanswer\tinput\tprofile\tparse_ns\tsolve_ns\tknown
+3\t1\t4361\tdata/3\t\t12000\t900\tpass
+3\t2\ta,\"b\"\tmy\\tinput\talice\t12000\t5\t
+"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use aoc_2023::solution::Part;
+
+/// An answer the puzzle text gives for one of its examples.
+#[derive(PartialEq, Debug)]
+pub struct Expected {
+    pub part: Part,
+    pub answer: String,
+    /// 1-based number of the example it most likely belongs to: the last one
+    /// shown before the answer.
+    pub sample: usize,
+}
+
+/// The text of every `<pre><code>` block in a saved puzzle page, in order.
+pub fn extract_samples(page: &str) -> Vec<String> {
+    blocks(page, "<pre><code>", "</code></pre>")
+        .into_iter()
+        .map(|(_, inner)| decode(inner))
+        .collect()
+}
+
+/// The final emphasised code in each part's description, which is where the
+/// puzzle text states the example's answer.
+pub fn expected_answers(page: &str) -> Vec<Expected> {
+    let samples = blocks(page, "<pre><code>", "</code></pre>");
+
+    blocks(page, "<article class=\"day-desc\">", "</article>")
+        .into_iter()
+        .zip(Part::ALL)
+        .filter_map(|((start, article), part)| {
+            let (offset, answer) = blocks(article, "<code><em>", "</em></code>").pop()?;
+            let position = start + offset;
+            let sample = samples
+                .iter()
+                .filter(|(sample, _)| *sample < position)
+                .count();
+
+            Some(Expected {
+                part,
+                answer: decode(answer),
+                sample: sample.max(1),
+            })
+        })
+        .collect()
+}
+
+/// Everything between each `open` and the following `close`, with the
+/// offset of the match in `text`.
+fn blocks<'a>(text: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut found = vec![];
+    let mut position = 0;
+
+    while let Some(start) = text[position..].find(open) {
+        let inner = position + start + open.len();
+        let Some(end) = text[inner..].find(close) else {
+            break;
+        };
+        found.push((position + start, &text[inner..inner + end]));
+        position = inner + end + close.len();
+    }
+
+    found
+}
+
+/// Drop markup such as `<em>` and decode the entities the puzzle pages use.
+fn decode(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find('>')
+            .map_or("", |end| &rest[start + end + 1..]);
+    }
+    text.push_str(rest);
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<main>
+<article class=\"day-desc\"><h2>--- Day 8: Haunted Wasteland ---</h2>
+<p>For example:</p>
+<pre><code>RL
+
+AAA = (BBB, CCC)
+</code></pre>
+<p>Here, <code>AAA</code> is the start.</p>
+<pre><code>LLR
+
+AAA = (BBB, BBB)
+</code></pre>
+<p>Starting at <code>AAA</code>, <em>how many steps</em>? Here, <code><em>6</em></code> steps.</p>
+</article>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<pre><code>LR
+
+11A = (11B, XXX)
+<em>22Z</em> = (22B, 22B)
+&lt;&amp;&gt;
+</code></pre>
+<p>So, in this example, you end up entirely on nodes that end in <code>Z</code> after <code><em>6</em></code> steps.</p>
+</article>
+</main>";
+
+    #[test]
+    fn test_extract_samples() {
+        assert_eq!(
+            extract_samples(PAGE),
+            vec![
+                "RL\n\nAAA = (BBB, CCC)\n",
+                "LLR\n\nAAA = (BBB, BBB)\n",
+                "LR\n\n11A = (11B, XXX)\n22Z = (22B, 22B)\n<&>\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expected_answers() {
+        assert_eq!(
+            expected_answers(PAGE),
+            vec![
+                Expected {
+                    part: Part::One,
+                    answer: String::from("6"),
+                    sample: 2,
+                },
+                Expected {
+                    part: Part::Two,
+                    answer: String::from("6"),
+                    sample: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_samples() {
+        assert!(extract_samples("<p>No examples</p>").is_empty());
+        assert!(expected_answers("<article class=\"day-desc\"></article>").is_empty());
+    }
+}
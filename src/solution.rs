@@ -0,0 +1,177 @@
+//! The interface each day implements, and the answers it gives.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::error::{Error, ParseError};
+use crate::utils::input::PuzzleInput;
+
+/// A single puzzle answer, whatever type the day happens to produce.
+///
+/// Numeric answers compare by value, so `Signed(5)` and `Unsigned(5)` are equal.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    /// A number that may be negative.
+    Signed(i64),
+    /// A count or other non-negative number.
+    Unsigned(u64),
+    /// Anything that isn't a number, such as letters read off a display.
+    Text(String),
+}
+
+impl Answer {
+    fn as_number(&self) -> Option<i128> {
+        match self {
+            Answer::Signed(value) => Some(*value as i128),
+            Answer::Unsigned(value) => Some(*value as i128),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(left), Answer::Text(right)) => left == right,
+            _ => self.as_number().is_some() && self.as_number() == other.as_number(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+
+            impl PartialEq<$source> for Answer {
+                fn eq(&self, other: &$source) -> bool {
+                    self.as_number() == Some(*other as i128)
+                }
+            }
+        )+
+    };
+}
+
+impl_answer_from!(Signed, i64, i32, i64, isize);
+impl_answer_from!(Unsigned, u64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(String::from(value))
+    }
+}
+
+/// One of the two halves of each day's puzzle.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub enum Part {
+    /// The first part.
+    One,
+    /// The second part, unlocked by solving the first.
+    Two,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("'{s}' is not a part, expected 1 or 2")),
+        }
+    }
+}
+
+/// A day's puzzle: parse the input once, then answer each part from it.
+pub trait Solution {
+    /// The model the input is parsed into.
+    type Input;
+
+    /// Read the puzzle input into the model.
+    fn parse(input: &PuzzleInput) -> Result<Self::Input, ParseError>;
+    /// Answer the first part.
+    fn part_1(input: &Self::Input) -> Result<Answer, Error>;
+    /// Answer the second part.
+    fn part_2(input: &Self::Input) -> Result<Answer, Error>;
+
+    /// Clear any memoisation, so that repeated runs are timed from cold.
+    fn reset() {}
+
+    /// Parse `source` and solve both parts in one go.
+    fn exec(source: &str) -> Result<(Answer, Answer), Error> {
+        let input = PuzzleInput::new(source);
+        let input = Self::parse(&input)?;
+        Ok((Self::part_1(&input)?, Self::part_2(&input)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from() {
+        assert_eq!(Answer::from(-3_i32), Answer::Signed(-3));
+        assert_eq!(Answer::from(3_usize), Answer::Unsigned(3));
+        assert_eq!(Answer::from("abc"), Answer::Text(String::from("abc")));
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::Signed(-3).to_string(), "-3");
+        assert_eq!(Answer::Unsigned(42).to_string(), "42");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!("1".parse(), Ok(Part::One));
+        assert_eq!("2".parse(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_answer_eq_number() {
+        assert_eq!(Answer::Unsigned(42), 42);
+        assert_eq!(Answer::Signed(42), 42_usize);
+        assert_eq!(Answer::Signed(42), Answer::Unsigned(42));
+        assert_ne!(Answer::Signed(-1), Answer::Unsigned(u64::MAX));
+        assert_ne!(Answer::from("42"), 42);
+    }
+}
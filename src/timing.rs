@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Summary of repeated timings of one phase.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        Stats {
+            runs: sorted.len(),
+            min: sorted[0],
+            median: percentile(&sorted, 50),
+            p95: percentile(&sorted, 95),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{nanos}ns")
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn test_stats_single_sample() {
+        let stats = Stats::from_samples(&millis(&[4]));
+        assert_eq!(stats.min, Duration::from_millis(4));
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.p95, Duration::from_millis(4));
+        assert_eq!(stats.max, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn test_stats_unsorted_samples() {
+        let stats = Stats::from_samples(&millis(&[9, 1, 5, 3, 7]));
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.p95, Duration::from_millis(9));
+        assert_eq!(stats.max, Duration::from_millis(9));
+    }
+
+    #[test]
+    fn test_stats_p95() {
+        let samples = (1..=100).collect::<Vec<_>>();
+        let stats = Stats::from_samples(&millis(&samples));
+        assert_eq!(stats.median, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_500)), "1.50µs");
+        assert_eq!(format_duration(Duration::from_micros(2_346)), "2.35ms");
+        assert_eq!(format_duration(Duration::from_millis(1_200)), "1.20s");
+    }
+}
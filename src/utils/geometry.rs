@@ -0,0 +1,302 @@
+//! Points, directions and shapes on the integer plane, with `y` growing
+//! downwards as it does in a puzzle input.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Sub};
+
+/// The integer types a [`Point`] can be made of.
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Zero.
+    const ZERO: Self;
+    /// One.
+    const ONE: Self;
+
+    /// How far apart `self` and `other` are, which can't overflow for
+    /// unsigned types.
+    fn distance(self, other: Self) -> Self {
+        self.max(other) - self.min(other)
+    }
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),+) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )+
+    };
+}
+
+impl_coordinate!(i32, i64, isize, u32, u64, usize);
+
+/// A position as column `x` and row `y`.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, Copy, Default)]
+pub struct Point<T> {
+    /// Column.
+    pub x: T,
+    /// Row.
+    pub y: T,
+}
+
+/// The step between two points, which adds and subtracts like one.
+pub type Vector<T> = Point<T>;
+
+impl<T> Point<T> {
+    /// The point at column `x`, row `y`.
+    pub const fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T: Coordinate> Point<T> {
+    /// The number of orthogonal steps between the points.
+    pub fn manhattan(self, other: Point<T>) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// The number of steps between the points when diagonal steps count as
+    /// one.
+    pub fn chebyshev(self, other: Point<T>) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+}
+
+impl Point<usize> {
+    /// Move by `offset`, or `None` if that would leave the positive
+    /// quadrant.
+    pub fn checked_add_signed(self, offset: Vector<isize>) -> Option<Point<usize>> {
+        Some(Point {
+            x: self.x.checked_add_signed(offset.x)?,
+            y: self.y.checked_add_signed(offset.y)?,
+        })
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// Scales a vector.
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, factor: T) -> Point<T> {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// One of the four ways along a grid.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Direction {
+    /// Up, towards lower `y`.
+    North,
+    /// Right, towards higher `x`.
+    East,
+    /// Down, towards higher `y`.
+    South,
+    /// Left, towards lower `x`.
+    West,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// A quarter turn anticlockwise.
+    pub fn turn_left(self) -> Direction {
+        self.reverse().turn_right()
+    }
+
+    /// The opposite direction.
+    pub fn reverse(self) -> Direction {
+        self.turn_right().turn_right()
+    }
+
+    /// A single step this way.
+    pub const fn offset(self) -> Vector<isize> {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+}
+
+/// The smallest rectangle holding a set of points, with both corners
+/// inclusive.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct BoundingBox<T> {
+    /// The top left corner.
+    pub min: Point<T>,
+    /// The bottom right corner.
+    pub max: Point<T>,
+}
+
+impl<T: Coordinate> BoundingBox<T> {
+    /// The box from `min` to `max`, both inclusive.
+    pub fn new(min: Point<T>, max: Point<T>) -> BoundingBox<T> {
+        BoundingBox { min, max }
+    }
+
+    /// The box around `points`, or `None` if there aren't any.
+    pub fn around(points: impl IntoIterator<Item = Point<T>>) -> Option<BoundingBox<T>> {
+        points.into_iter().fold(None, |bounds, point| {
+            Some(match bounds {
+                None => BoundingBox::new(point, point),
+                Some(BoundingBox { min, max }) => BoundingBox::new(
+                    Point::new(min.x.min(point.x), min.y.min(point.y)),
+                    Point::new(max.x.max(point.x), max.y.max(point.y)),
+                ),
+            })
+        })
+    }
+
+    /// Whether `point` is inside or on the edge.
+    pub fn contains(&self, point: Point<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+}
+
+/// Twice the area of the polygon with these corners in order, by the
+/// shoelace formula. Doubling keeps it whole for whole coordinates, and it's
+/// the same whichever way round the corners go.
+pub fn double_area<T: Coordinate>(vertices: &[Point<T>]) -> T {
+    let (mut clockwise, mut anticlockwise) = (T::ZERO, T::ZERO);
+
+    for (index, &point) in vertices.iter().enumerate() {
+        let next = vertices[(index + 1) % vertices.len()];
+        clockwise = clockwise + point.x * next.y;
+        anticlockwise = anticlockwise + next.x * point.y;
+    }
+
+    clockwise.distance(anticlockwise)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let point = Point::new(3, -2);
+
+        assert_eq!(point + Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(point - Point::new(1, 1), Point::new(2, -3));
+        assert_eq!(point * 3, Point::new(9, -6));
+        assert_eq!(point.to_string(), "(3, -2)");
+        assert_eq!(
+            Point::new(0_usize, 2).checked_add_signed(Direction::North.offset()),
+            Some(Point::new(0, 1))
+        );
+        assert_eq!(
+            Point::new(0_usize, 2).checked_add_signed(Direction::West.offset()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point::new(1_usize, 6), Point::new(5_usize, 3));
+
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(b.manhattan(a), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Point::new(-2, 0).manhattan(Point::new(2, -1)), 5);
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(
+                direction.offset() + direction.reverse().offset(),
+                Point::new(0, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let bounds =
+            BoundingBox::around([Point::new(2, 5), Point::new(-1, 3), Point::new(0, 7)]).unwrap();
+
+        assert_eq!(
+            bounds,
+            BoundingBox::new(Point::new(-1, 3), Point::new(2, 7))
+        );
+        assert_eq!((bounds.width(), bounds.height()), (4, 5));
+        assert!(bounds.contains(Point::new(2, 7)));
+        assert!(!bounds.contains(Point::new(3, 7)));
+        assert_eq!(BoundingBox::<i32>::around([]), None);
+    }
+
+    #[test]
+    fn test_double_area() {
+        let square = [
+            Point::new(0_usize, 0),
+            Point::new(4, 0),
+            Point::new(4, 3),
+            Point::new(0, 3),
+        ];
+        let triangle = [Point::new(0, 0), Point::new(0, 3), Point::new(-3, 0)];
+
+        assert_eq!(double_area(&square), 24);
+        assert_eq!(
+            double_area(&[square[3], square[2], square[1], square[0]]),
+            24
+        );
+        assert_eq!(double_area(&triangle), 9);
+    }
+}
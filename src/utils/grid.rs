@@ -0,0 +1,386 @@
+//! Rectangular maps of tiles, such as the character maps many days are drawn
+//! as.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+use crate::utils::geometry::{Direction, Point, Vector};
+use crate::utils::parse::LineCursor;
+
+/// A rectangle of cells addressed by column `x` and row `y`, both counted
+/// from the top left.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Steps to the eight cells sharing an edge or corner, clockwise from north.
+const SURROUNDING: [Vector<isize>; 8] = [
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+    Point::new(-1, -1),
+];
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Read a grid with a line per row, turning each character into a cell
+    /// with `cell`, which explains what it expected when it returns an error.
+    /// Every row must be as long as the first.
+    pub fn parse<'a>(
+        lines: impl Into<LineCursor<'a>>,
+        cell: impl Fn(char) -> Result<T, &'static str>,
+    ) -> Result<Grid<T>, ParseError> {
+        let lines = lines.into().collect::<Vec<_>>();
+        if lines.len() == 1 && lines[0].is_empty() {
+            return Ok(Grid {
+                width: 0,
+                height: 0,
+                cells: vec![],
+            });
+        }
+
+        let rows = lines
+            .iter()
+            .map(|line| {
+                line.chars()
+                    .map(|(c, at)| cell(c).map_err(|message| at.error(message)))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let width = rows[0].len();
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            return Err(lines[index].error(format!("expected a row of {width} tiles")));
+        }
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at `point`, or `None` outside the grid.
+    pub fn get(&self, point: Point<usize>) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    /// The cell at `point` to change in place, or `None` outside the grid.
+    pub fn get_mut(&mut self, point: Point<usize>) -> Option<&mut T> {
+        self.index_of(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Replace the cell at `point`, returning what was there. Outside the
+    /// grid nothing changes and `None` is returned.
+    pub fn set(&mut self, point: Point<usize>, value: T) -> Option<T> {
+        self.get_mut(point)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Whether `point` is inside the grid.
+    pub fn contains(&self, point: Point<usize>) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    fn index_of(&self, point: Point<usize>) -> Option<usize> {
+        self.contains(point)
+            .then_some(point.y * self.width + point.x)
+    }
+
+    /// The position `offset` away from `point`, if it's in the grid.
+    pub fn offset(&self, point: Point<usize>, offset: Vector<isize>) -> Option<Point<usize>> {
+        point
+            .checked_add_signed(offset)
+            .filter(|&moved| self.contains(moved))
+    }
+
+    /// The position a step from `point` in `direction`, if it's in the grid.
+    pub fn step(&self, point: Point<usize>, direction: Direction) -> Option<Point<usize>> {
+        self.offset(point, direction.offset())
+    }
+
+    /// The positions sharing an edge with `point`, clockwise from north.
+    pub fn neighbours_4(&self, point: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// The positions sharing an edge or a corner with `point`, clockwise
+    /// from north.
+    pub fn neighbours_8(&self, point: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        SURROUNDING
+            .into_iter()
+            .filter_map(move |offset| self.offset(point, offset))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (Point::new(index % self.width, index / self.width), cell))
+    }
+
+    /// Row `y`, or `None` outside the grid.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Each row, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` can't take a width of 0, which an empty grid has.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Column `x` from top to bottom, which is empty outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Each column, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Build a `width` by `height` grid taking each cell from this one.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> usize) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.cells[source(x, y)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Swap rows for columns, mirroring along the leading diagonal.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| x * self.width + y)
+    }
+
+    /// Turn a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| {
+            (self.height - 1 - x) * self.width + y
+        })
+    }
+
+    /// Turn a quarter turn anticlockwise.
+    pub fn rotate_anticlockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| {
+            x * self.width + self.width - 1 - y
+        })
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.width, self.height, |x, y| {
+            y * self.width + self.width - 1 - x
+        })
+    }
+
+    /// Mirror top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.width, self.height, |x, y| {
+            (self.height - 1 - y) * self.width + x
+        })
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    /// The cell at `point`, panicking outside the grid.
+    fn index(&self, point: Point<usize>) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside a {width}x{height} grid"))
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside a {width}x{height} grid"))
+    }
+}
+
+/// Draws a line per row, with each cell as it displays.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(source: &str) -> Grid<char> {
+        Grid::parse(source, Ok).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = chars("abc\ndef");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(grid[Point::new(1, 0)], 'b');
+        assert_eq!(chars("").height(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let bits = |c| match c {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            _ => Err("expected '0' or '1'"),
+        };
+
+        let error = Grid::parse("01\n0x", bits).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+
+        let error = Grid::parse("01\n011", bits).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected a row of 2 tiles");
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid = Grid::new(2, 2, '.');
+
+        assert_eq!(grid.set(Point::new(1, 0), '#'), Some('.'));
+        assert_eq!(grid.set(Point::new(2, 0), '#'), None);
+        grid[Point::new(0, 1)] = '@';
+        assert_eq!(grid.to_string(), ".#\n@.");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = chars("abc\ndef\nghi");
+
+        let points = |points: &[(usize, usize)]| {
+            points
+                .iter()
+                .map(|&(x, y)| Point::new(x, y))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            grid.neighbours_4(Point::new(0, 0)).collect::<Vec<_>>(),
+            points(&[(1, 0), (0, 1)])
+        );
+        assert_eq!(grid.neighbours_4(Point::new(1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours_8(Point::new(2, 1)).collect::<Vec<_>>(),
+            points(&[(2, 0), (2, 2), (1, 2), (1, 1), (1, 0)])
+        );
+        assert_eq!(grid.neighbours_8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.step(Point::new(0, 0), Direction::West), None);
+        assert_eq!(
+            grid.offset(Point::new(0, 0), Point::new(2, 2)),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(grid.offset(Point::new(0, 0), Point::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = chars("abc\ndef");
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(grid.iter().nth(4), Some((Point::new(1, 1), &'e')));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = chars("abc\ndef");
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+    }
+}
@@ -0,0 +1,217 @@
+//! Reading puzzle inputs.
+
+use crate::error::ParseError;
+use crate::utils::parse::LineCursor;
+
+/// Puzzle input with line endings and stray whitespace cleaned up, so that
+/// inputs saved on Windows or through a browser parse the same as the
+/// originals.
+#[derive(PartialEq, Debug, Clone)]
+pub struct PuzzleInput {
+    text: String,
+    /// Number of blank lines dropped from the start.
+    skipped: usize,
+}
+
+/// A run of non-blank lines, remembering where it started in the input.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Block<'a> {
+    /// Number of lines before this block in the original source.
+    pub offset: usize,
+    /// The block's lines, without the blank lines around it.
+    pub text: &'a str,
+}
+
+impl PuzzleInput {
+    /// Normalise leniently: drop a byte order mark, CRLF line endings,
+    /// trailing whitespace and blank lines at either end.
+    pub fn new(source: &str) -> PuzzleInput {
+        let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+        let lines = source.split('\n').map(str::trim_end).collect::<Vec<_>>();
+        let start = lines.iter().position(|line| !line.is_empty());
+        let end = lines.iter().rposition(|line| !line.is_empty());
+
+        let (text, skipped) = match (start, end) {
+            (Some(start), Some(end)) => (lines[start..=end].join("\n"), start),
+            _ => (String::new(), 0),
+        };
+
+        PuzzleInput { text, skipped }
+    }
+
+    /// Normalise, but reject anything that would have needed cleaning up
+    /// other than a single final newline.
+    pub fn strict(source: &str) -> Result<PuzzleInput, ParseError> {
+        let body = source.strip_suffix('\n').unwrap_or(source);
+        let lines = body.split('\n').collect::<Vec<_>>();
+
+        for (index, line) in lines.iter().enumerate() {
+            let error =
+                |fragment, message| Err(ParseError::at(line, fragment, message).shifted(index));
+
+            if let Some(bom) = line.strip_prefix('\u{feff}') {
+                return error(&line[..line.len() - bom.len()], "byte order mark");
+            }
+            if let Some(position) = line.find('\r') {
+                return error(&line[position..], "carriage return");
+            }
+            let trimmed = line.trim_end();
+            if trimmed.len() != line.len() {
+                return error(&line[trimmed.len()..], "trailing whitespace");
+            }
+        }
+
+        if lines.first().is_some_and(|line| line.is_empty()) && lines.len() > 1 {
+            return Err(ParseError::at("", "", "blank line at the start"));
+        }
+        if lines.last().is_some_and(|line| line.is_empty()) && lines.len() > 1 {
+            return Err(ParseError::at("", "", "blank line at the end").shifted(lines.len() - 1));
+        }
+
+        Ok(PuzzleInput::new(source))
+    }
+
+    /// The whole normalised input.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Each line, without its line ending, numbered by where it was in the
+    /// original source, counting the blank lines dropped from the start.
+    pub fn lines(&self) -> LineCursor<'_> {
+        LineCursor::starting_at(&self.text, self.skipped)
+    }
+
+    /// Split into blocks separated by one or more blank lines.
+    pub fn blocks(&self) -> Vec<Block<'_>> {
+        let mut blocks = vec![];
+        let mut start = None;
+        let mut position = 0;
+
+        for (index, line) in self.text.split('\n').enumerate() {
+            match (line.is_empty(), start) {
+                (false, None) => start = Some((self.skipped + index, position)),
+                (true, Some((offset, from))) => {
+                    blocks.push(Block {
+                        offset,
+                        text: &self.text[from..position - 1],
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+            position += line.len() + 1;
+        }
+
+        if let Some((offset, from)) = start {
+            blocks.push(Block {
+                offset,
+                text: &self.text[from..],
+            });
+        }
+
+        blocks
+    }
+}
+
+impl<'a> Block<'a> {
+    /// Its lines, numbered by where they are in the whole input.
+    pub fn lines(&self) -> LineCursor<'a> {
+        LineCursor::starting_at(self.text, self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_normalises_line_endings() {
+        let input = PuzzleInput::new("a b\r\nc  \r\n\r\n");
+        assert_eq!(input.as_str(), "a b\nc");
+        assert_eq!(
+            input.lines().map(|line| line.as_str()).collect::<Vec<_>>(),
+            vec!["a b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_new_trims_blank_lines_and_bom() {
+        assert_eq!(
+            PuzzleInput::new("\u{feff}\n\nx\n\ny\n\n").as_str(),
+            "x\n\ny"
+        );
+        assert_eq!(PuzzleInput::new("\n \n").as_str(), "");
+    }
+
+    #[test]
+    fn test_lines_count_dropped_blank_lines() {
+        let input = PuzzleInput::new("\n\nx\ny\n\n");
+        let error = input
+            .lines()
+            .map(|line| line.number::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+
+        assert_eq!(error.line, 3);
+        assert_eq!(input.blocks()[0].offset, 2);
+    }
+
+    #[test]
+    fn test_strict_accepts_clean_input() {
+        assert_eq!(
+            PuzzleInput::strict("a\n\nb\n"),
+            Ok(PuzzleInput::new("a\n\nb"))
+        );
+        assert_eq!(PuzzleInput::strict("a\nb"), Ok(PuzzleInput::new("a\nb")));
+    }
+
+    #[test]
+    fn test_strict_rejects_stray_whitespace() {
+        let error = PuzzleInput::strict("ab\r\ncd\r\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+
+        let error = PuzzleInput::strict("ab\ncd \n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, " "));
+
+        assert_eq!(PuzzleInput::strict("ab\n\n").unwrap_err().line, 2);
+        assert_eq!(PuzzleInput::strict("\nab\n").unwrap_err().line, 1);
+        assert_eq!(PuzzleInput::strict("\u{feff}ab").unwrap_err().column, 1);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = PuzzleInput::new("a\nb\n\n\nc\n\nd\ne");
+        let blocks = input.blocks();
+
+        assert_eq!(
+            blocks,
+            vec![
+                Block {
+                    offset: 0,
+                    text: "a\nb"
+                },
+                Block {
+                    offset: 4,
+                    text: "c"
+                },
+                Block {
+                    offset: 6,
+                    text: "d\ne"
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_block_errors_are_shifted() {
+        let input = PuzzleInput::new("1\n\n2\nx");
+        let error = input.blocks()[1]
+            .lines()
+            .map(|line| line.number::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+
+        assert_eq!(error.line, 4);
+    }
+}
@@ -0,0 +1,384 @@
+//! Half-open intervals of integers, sets of them, and maps that move whole
+//! intervals at once.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub};
+
+/// The types an [`Interval`] can span, which in practice are integers.
+pub trait Endpoint: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Endpoint for T {}
+
+/// The values from `start` up to but not including `end`.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Interval<T> {
+    /// The first value.
+    pub start: T,
+    /// The first value after the last one.
+    pub end: T,
+}
+
+impl<T: Endpoint> Interval<T> {
+    /// The values from `start` up to but not including `end`, which is empty
+    /// unless `start < end`.
+    pub fn new(start: T, end: T) -> Interval<T> {
+        Interval { start, end }
+    }
+
+    /// The `length` values from `start`.
+    pub fn with_length(start: T, length: T) -> Interval<T> {
+        Interval::new(start, start + length)
+    }
+
+    /// Whether there are no values in it.
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// The number of values in it.
+    pub fn len(&self) -> T {
+        self.end.max(self.start) - self.start
+    }
+
+    /// Whether `value` is in it.
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values in both, or `None` if there aren't any.
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// The interval moved so that `from` lands on `to`, where `from` is no
+    /// later than `start`.
+    pub fn moved(&self, from: T, to: T) -> Interval<T> {
+        Interval::new(self.start - from + to, self.end - from + to)
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// A set of values held as the fewest intervals that cover it, in order,
+/// with none empty and none touching another.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Endpoint> RangeSet<T> {
+    /// The empty set.
+    pub fn new() -> RangeSet<T> {
+        RangeSet { intervals: vec![] }
+    }
+
+    /// The intervals covering the set, in order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /// Whether there are no values in it.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Whether `value` is in it.
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals
+            .iter()
+            .any(|interval| interval.contains(value))
+    }
+
+    /// The lowest value, if there is one.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// Add the values in `interval`.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        self.intervals.push(interval);
+        self.normalise();
+    }
+
+    /// The values in either set.
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    /// The values in both sets.
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let (left, right) = (&self.intervals, &other.intervals);
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = vec![];
+
+        while i < left.len() && j < right.len() {
+            intervals.extend(left[i].intersection(&right[j]));
+            if left[i].end < right[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        RangeSet { intervals }
+    }
+
+    /// The values in this set but not in `other`.
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut intervals = vec![];
+
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            for removed in &other.intervals {
+                if removed.end <= start || removed.start >= interval.end {
+                    continue;
+                }
+                if removed.start > start {
+                    intervals.push(Interval::new(start, removed.start));
+                }
+                start = removed.end;
+            }
+            if start < interval.end {
+                intervals.push(Interval::new(start, interval.end));
+            }
+        }
+
+        RangeSet { intervals }
+    }
+
+    /// Sort, then merge any intervals that overlap or touch.
+    fn normalise(&mut self) {
+        self.intervals.retain(|interval| !interval.is_empty());
+        self.intervals.sort_by_key(|interval| interval.start);
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+}
+
+impl<T: Endpoint> FromIterator<Interval<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = RangeSet {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalise();
+        set
+    }
+}
+
+impl<T: Endpoint> From<Interval<T>> for RangeSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        [interval].into_iter().collect()
+    }
+}
+
+/// A function that moves each of a few disjoint intervals by its own offset
+/// and leaves every other value where it is.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct IntervalMap<T> {
+    /// Each source interval with where its start goes, in order.
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: Endpoint> IntervalMap<T> {
+    /// The map that leaves every value where it is.
+    pub fn new() -> IntervalMap<T> {
+        IntervalMap { pieces: vec![] }
+    }
+
+    /// Move `source` so that it starts at `destination`. Values already
+    /// mapped by an earlier piece stay as they were.
+    pub fn insert(&mut self, source: Interval<T>, destination: T) {
+        let unmapped = RangeSet::from(source).difference(&self.domain());
+
+        for part in unmapped.intervals() {
+            self.pieces
+                .push((*part, part.start - source.start + destination));
+        }
+        self.pieces.sort_by_key(|(source, _)| source.start);
+    }
+
+    /// Each source interval with where its start goes, in order.
+    pub fn pieces(&self) -> &[(Interval<T>, T)] {
+        &self.pieces
+    }
+
+    /// The values that the map moves.
+    pub fn domain(&self) -> RangeSet<T> {
+        self.pieces.iter().map(|(source, _)| *source).collect()
+    }
+
+    /// Where `value` goes.
+    pub fn apply(&self, value: T) -> T {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |(source, destination)| {
+                value - source.start + *destination
+            })
+    }
+
+    /// Where every value in `set` goes.
+    pub fn apply_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let moved = self.pieces.iter().flat_map(|(source, destination)| {
+            set.intersection(&RangeSet::from(*source))
+                .intervals
+                .into_iter()
+                .map(|part| part.moved(source.start, *destination))
+        });
+        let unmoved = set.difference(&self.domain());
+
+        moved.chain(unmoved.intervals).collect()
+    }
+
+    /// The map that applies this one and then `then`.
+    pub fn compose(&self, then: &IntervalMap<T>) -> IntervalMap<T> {
+        let mut composed = IntervalMap::new();
+
+        for (source, destination) in &self.pieces {
+            let image = RangeSet::from(source.moved(source.start, *destination));
+            let back = |part: &Interval<T>| part.moved(*destination, source.start);
+
+            for (next, next_destination) in &then.pieces {
+                for part in image.intersection(&RangeSet::from(*next)).intervals() {
+                    composed.insert(back(part), part.start - next.start + *next_destination);
+                }
+            }
+            for part in image.difference(&then.domain()).intervals() {
+                composed.insert(back(part), part.start);
+            }
+        }
+
+        let domain = self.domain();
+        for (next, next_destination) in &then.pieces {
+            for part in RangeSet::from(*next).difference(&domain).intervals() {
+                composed.insert(*part, part.start - next.start + *next_destination);
+            }
+        }
+
+        composed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> RangeSet<i64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::with_length(5_usize, 3);
+
+        assert_eq!(interval, Interval::new(5, 8));
+        assert_eq!(interval.len(), 3);
+        assert!(interval.contains(7));
+        assert!(!interval.contains(8));
+        assert_eq!(Interval::new(8_usize, 5).len(), 0);
+        assert_eq!(
+            interval.intersection(&Interval::new(7, 20)),
+            Some(Interval::new(7, 8))
+        );
+        assert_eq!(interval.intersection(&Interval::new(8, 20)), None);
+        assert_eq!(interval.moved(5, 1), Interval::new(1, 4));
+        assert_eq!(interval.to_string(), "[5, 8)");
+    }
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(
+            set(&[(5, 8), (1, 3), (3, 4), (7, 10), (12, 12)]).intervals(),
+            &[Interval::new(1, 4), Interval::new(5, 10)]
+        );
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let left = set(&[(0, 5), (10, 15)]);
+        let right = set(&[(3, 12), (14, 20)]);
+
+        assert_eq!(left.union(&right), set(&[(0, 20)]));
+        assert_eq!(
+            left.intersection(&right),
+            set(&[(3, 5), (10, 12), (14, 15)])
+        );
+        assert_eq!(left.difference(&right), set(&[(0, 3), (12, 14)]));
+        assert_eq!(right.difference(&left), set(&[(5, 10), (15, 20)]));
+        assert_eq!(left.difference(&left), RangeSet::new());
+        assert_eq!(left.min(), Some(0));
+        assert!(left.contains(14) && !left.contains(15));
+    }
+
+    #[test]
+    fn test_map_apply() {
+        let mut map = IntervalMap::new();
+        map.insert(Interval::with_length(98, 2), 50);
+        map.insert(Interval::with_length(50, 48), 52);
+
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(14), 14);
+        assert_eq!(map.apply(97), 99);
+        assert_eq!(map.apply(98), 50);
+        assert_eq!(map.apply(100), 100);
+        assert_eq!(
+            map.apply_set(&set(&[(45, 55), (99, 101)])),
+            set(&[(45, 50), (51, 57), (100, 101)])
+        );
+    }
+
+    #[test]
+    fn test_map_insert_keeps_earlier_pieces() {
+        let mut map = IntervalMap::new();
+        map.insert(Interval::new(10, 20), 110);
+        map.insert(Interval::new(0, 30), 200);
+
+        assert_eq!(map.apply(15), 115);
+        assert_eq!(map.apply(5), 205);
+        assert_eq!(map.apply(25), 225);
+        assert_eq!(map.pieces().len(), 3);
+    }
+
+    #[test]
+    fn test_compose() {
+        let mut first = IntervalMap::new();
+        first.insert(Interval::new(0, 10), 20);
+        first.insert(Interval::new(30, 35), 0);
+        let mut second = IntervalMap::new();
+        second.insert(Interval::new(5, 25), 105);
+
+        let composed = first.compose(&second);
+        for value in -5..50 {
+            assert_eq!(
+                composed.apply(value),
+                second.apply(first.apply(value)),
+                "{value}"
+            );
+        }
+
+        let seeds = set(&[(-5, 50)]);
+        assert_eq!(
+            composed.apply_set(&seeds),
+            second.apply_set(&first.apply_set(&seeds))
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! Number theory for puzzles about cycles and periods, for every integer
+//! width from `u8` to `u128`.
+
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// The primitive integer types.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    /// Zero.
+    const ZERO: Self;
+    /// One.
+    const ONE: Self;
+
+    /// `self * other`, or `None` on overflow.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    /// The absolute value, or `None` if it doesn't fit.
+    fn checked_abs(self) -> Option<Self>;
+    /// The remainder of dividing by `modulus`, from zero up to `modulus`.
+    fn rem_euclid(self, modulus: Self) -> Self;
+}
+
+/// The primitive integer types that can be negative.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($abs:expr; $($t:ty),+) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_abs(self) -> Option<Self> {
+                    $abs(self)
+                }
+
+                fn rem_euclid(self, modulus: Self) -> Self {
+                    <$t>::rem_euclid(self, modulus)
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(Some; u8, u16, u32, u64, u128, usize);
+impl_integer!(|value: Self| value.checked_abs(); i8, i16, i32, i64, i128, isize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// Greatest common divisor, which is never negative and is zero only when
+/// both are. `None` if it doesn't fit, as for `gcd(i64::MIN, 0)`.
+pub fn checked_gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a.checked_abs()?, b.checked_abs()?);
+
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    Some(a)
+}
+
+/// Least common multiple, which is never negative and is zero when either
+/// is. `None` if it doesn't fit.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    (a.checked_abs()? / checked_gcd(a, b)?).checked_mul(b.checked_abs()?)
+}
+
+/// Least common multiple of all of `values`, or 1 if there are none. `None`
+/// if it doesn't fit.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |lcm, value| checked_lcm(lcm, value))
+}
+
+/// `(g, x, y)` where `g` is the greatest common divisor and `a * x + b * y
+/// = g`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `(a + b) % modulus` for `a` and `b` already reduced, without overflowing.
+fn add_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a * b) % modulus` for `a` and `b` already reduced, falling back to
+/// doubling and adding when the product would overflow.
+fn mul_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    let two = T::ONE + T::ONE;
+    let (mut a, mut b, mut result) = (a, b, T::ZERO);
+    while b != T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b = b / two;
+    }
+
+    result
+}
+
+/// `base` to the power of `exponent`, modulo `modulus`.
+///
+/// Panics unless `modulus` is positive and `exponent` isn't negative.
+pub fn mod_pow<T: Integer>(base: T, exponent: T, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "the modulus must be positive");
+    assert!(exponent >= T::ZERO, "the exponent can't be negative");
+
+    let two = T::ONE + T::ONE;
+    let (mut base, mut exponent) = (base.rem_euclid(modulus), exponent);
+    let mut result = T::ONE % modulus;
+
+    while exponent != T::ZERO {
+        if exponent % two == T::ONE {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent = exponent / two;
+    }
+
+    result
+}
+
+/// The `x` from zero up to `modulus` with `a * x ≡ 1`, or `None` if `a`
+/// and `modulus` share a factor.
+///
+/// Panics unless `modulus` is positive.
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    assert!(modulus > T::ZERO, "the modulus must be positive");
+
+    // Extended Euclid, keeping only `a`'s coefficient and keeping it reduced
+    // so that it works for unsigned types too.
+    let (mut old_r, mut r) = (modulus, a.rem_euclid(modulus));
+    let (mut old_x, mut x) = (T::ZERO, T::ONE % modulus);
+
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        let step = mul_mod(quotient % modulus, x, modulus);
+        (old_x, x) = (x, add_mod(old_x, modulus - step, modulus));
+    }
+
+    (old_r == T::ONE).then_some(old_x)
+}
+
+/// The `(residue, modulus)` of the numbers congruent to each `(residue,
+/// modulus)` given, by the Chinese remainder theorem. The moduli needn't be
+/// coprime, so the result's modulus is their least common multiple. `None`
+/// if the congruences contradict each other or the result doesn't fit.
+///
+/// Panics unless every modulus is positive.
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    congruences
+        .iter()
+        .try_fold((T::ZERO, T::ONE), |(a1, m1), &(a2, m2)| {
+            assert!(m2 > T::ZERO, "the modulus must be positive");
+
+            let a2 = a2.rem_euclid(m2);
+            let g = checked_gcd(m1, m2)?;
+            // x = a1 + m1 * k, where m1 * k ≡ a2 - a1 (mod m2).
+            let difference = add_mod(a2, m2 - a1 % m2, m2);
+            if difference % g != T::ZERO {
+                return None;
+            }
+
+            let reduced = m2 / g;
+            let k = mul_mod(difference / g, mod_inverse(m1 / g, reduced)?, reduced);
+            let lcm = (m1 / g).checked_mul(m2)?;
+
+            Some((a1 + m1 * k, lcm))
+        })
+}
+
+/// The largest integer whose square is no more than `n`.
+///
+/// Panics if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    assert!(
+        n >= T::ZERO,
+        "can't take the square root of a negative number"
+    );
+
+    let two = T::ONE + T::ONE;
+    if n < two {
+        return n;
+    }
+
+    // Newton's method, from an overestimate that can't overflow.
+    let mut x = n / two + T::ONE;
+    let mut y = (x + n / x) / two;
+    while y < x {
+        x = y;
+        y = (x + n / x) / two;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(checked_gcd(12, 18), Some(6));
+        assert_eq!(checked_gcd(-12, 18), Some(6));
+        assert_eq!(checked_gcd(0_u8, 0), Some(0));
+        assert_eq!(checked_gcd(i64::MIN, 0), None);
+        assert_eq!(checked_lcm(4, -6), Some(12));
+        assert_eq!(checked_lcm(0_u32, 6), Some(0));
+        assert_eq!(checked_lcm(200_u8, 3), None);
+        assert_eq!(lcm_all([2_u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+        assert_eq!(
+            lcm_all([u64::MAX as u128, 3, u64::MAX as u128 + 2]),
+            Some(u64::MAX as u128 * (u64::MAX as u128 + 2))
+        );
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (7, 0), (0, -7), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+
+            assert_eq!(Some(g), checked_gcd(a, b), "{a}, {b}");
+            assert_eq!(a * x + b * y, g, "{a}, {b}");
+        }
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(u128::MAX - 1, 2, u128::MAX), 1);
+        assert_eq!(mod_pow(2_u128, 127, u128::MAX), 1 << 127);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6_u32, 9), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+
+        let modulus = u128::MAX - 158; // 2^128 - 159, which is prime
+        let inverse = mod_inverse(12_345, modulus).unwrap();
+        assert_eq!(mul_mod(12_345, inverse, modulus), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3_u64, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(2, 4), (3, 6)]), None);
+        assert_eq!(crt(&[(-1, 4)]), Some((3, 4)));
+        assert_eq!(crt::<i32>(&[]), Some((0, 1)));
+
+        let (big, bigger) = (u64::MAX as u128, u64::MAX as u128 - 1);
+        assert_eq!(crt(&[(5, big), (5, bigger)]), Some((5, big * bigger)));
+        assert_eq!(
+            crt(&[(0, 1 << 100), (0, 3 << 100)]),
+            Some((0, 3_u128 << 100))
+        );
+        assert_eq!(crt(&[(1_u8, 200), (0, 3)]), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        let cases = [
+            (0, 0),
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (4, 2),
+            (15, 3),
+            (16, 4),
+            (99, 9),
+        ];
+        for (n, root) in cases {
+            assert_eq!(isqrt(n), root, "{n}");
+        }
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(isqrt(i64::MAX), 3_037_000_499);
+    }
+}
@@ -0,0 +1,88 @@
+//! Helpers shared between the days.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod parse;
+
+/// Inputs, answers files and the sample manifest compiled in by the
+/// `embed-inputs` feature, by file name in `data/`.
+static EMBEDDED: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+/// The directory inputs are read from, relative to the working directory.
+pub const DATA_DIR: &str = "data";
+
+/// Where the input named `day`, such as `5` or `5_sample_1`, is kept.
+pub fn input_path(day: &str) -> PathBuf {
+    Path::new(DATA_DIR).join(day)
+}
+
+/// Read an input by name, panicking if it's missing.
+#[cfg(test)]
+pub fn read_input(day: &str) -> String {
+    let filepath = input_path(day);
+    let err = format!("Unable to find '{}'", filepath.display());
+    try_read_input(&filepath).expect(&err)
+}
+
+/// Read an input, preferring a copy embedded in the binary for paths in
+/// `data/` and falling back to the filesystem.
+pub fn try_read_input(path: &Path) -> io::Result<String> {
+    match embedded(path) {
+        Some(contents) => Ok(String::from(contents)),
+        None => fs::read_to_string(path),
+    }
+}
+
+/// Read a data file that commands also write, such as an answers file,
+/// preferring the filesystem so that it's never stale and falling back to a
+/// copy embedded in the binary.
+pub fn read_data_file(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            embedded(path).map(String::from).ok_or(error)
+        }
+        result => result,
+    }
+}
+
+fn embedded(path: &Path) -> Option<&'static str> {
+    let name = path.strip_prefix(DATA_DIR).ok()?.to_str()?;
+
+    EMBEDDED
+        .iter()
+        .find(|(embedded, _)| *embedded == name)
+        .map(|(_, contents)| *contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_inputs() {
+        assert_eq!(embedded(Path::new("elsewhere/1")), None);
+        assert_eq!(embedded(Path::new("data/no_such_input")), None);
+
+        for name in ["data/1_sample_1", "data/answers.toml", "data/samples.toml"] {
+            let file = embedded(Path::new(name));
+            if cfg!(feature = "embed-inputs") {
+                assert_eq!(file, Some(fs::read_to_string(name).unwrap().as_str()));
+            } else {
+                assert_eq!(file, None);
+            }
+        }
+        assert_eq!(
+            read_data_file(Path::new("data/no_such_file"))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
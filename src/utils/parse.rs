@@ -0,0 +1,424 @@
+//! Pulling numbers and fields out of puzzle lines, with every error pointing
+//! at the text it's about.
+
+use std::str::FromStr;
+
+use crate::error::ParseError;
+use crate::utils::input::{Block, PuzzleInput};
+
+/// Part of a line that remembers the whole line and which line of the input
+/// it is, so that errors about it land on the right line and column.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    line: &'a str,
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// All of `line`, counted as the first line until a caller shifts its
+    /// errors into place.
+    pub fn new(line: &'a str) -> Cursor<'a> {
+        Cursor {
+            line,
+            text: line,
+            offset: 0,
+        }
+    }
+
+    fn part(&self, text: &'a str) -> Cursor<'a> {
+        Cursor { text, ..*self }
+    }
+
+    /// The text it covers.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Whether it covers no text.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// An error about all of its text.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.line, self.text, message).shifted(self.offset)
+    }
+
+    /// The rest after `prefix`, failing if it doesn't start with it.
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Cursor<'a>, ParseError> {
+        self.text
+            .strip_prefix(prefix)
+            .map(|rest| self.part(rest))
+            .ok_or_else(|| self.error(format!("expected '{prefix}'")))
+    }
+
+    /// The rest before `suffix`, failing if it doesn't end with it.
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Cursor<'a>, ParseError> {
+        self.text
+            .strip_suffix(suffix)
+            .map(|rest| self.part(rest))
+            .ok_or_else(|| self.error(format!("expected '{suffix}'")))
+    }
+
+    /// Without whitespace at either end.
+    pub fn trim(&self) -> Cursor<'a> {
+        self.part(self.text.trim())
+    }
+
+    /// The parts between each `delimiter`.
+    pub fn split(&self, delimiter: &'a str) -> impl Iterator<Item = Cursor<'a>> + 'a {
+        let this = *self;
+        self.text.split(delimiter).map(move |text| this.part(text))
+    }
+
+    /// The parts between runs of whitespace.
+    pub fn words(&self) -> impl Iterator<Item = Cursor<'a>> + 'a {
+        let this = *self;
+        self.text
+            .split_whitespace()
+            .map(move |text| this.part(text))
+    }
+
+    /// The rest after the first `count` characters.
+    pub fn skip_chars(&self, count: usize) -> Cursor<'a> {
+        let start = self
+            .text
+            .char_indices()
+            .nth(count)
+            .map_or(self.text.len(), |(index, _)| index);
+        self.part(&self.text[start..])
+    }
+
+    /// The leading characters that `accept` takes.
+    pub fn take_while(&self, accept: impl Fn(char) -> bool) -> Cursor<'a> {
+        let end = self.text.find(|c| !accept(c)).unwrap_or(self.text.len());
+        self.part(&self.text[..end])
+    }
+
+    /// Each character, with a cursor covering just that character.
+    pub fn chars(&self) -> impl Iterator<Item = (char, Cursor<'a>)> + 'a {
+        let this = *self;
+        self.text
+            .char_indices()
+            .map(move |(index, c)| (c, this.part(&this.text[index..index + c.len_utf8()])))
+    }
+
+    /// All of its text as a number.
+    pub fn number<T: FromStr>(&self) -> Result<T, ParseError> {
+        self.text
+            .parse()
+            .map_err(|_| self.error("expected a number"))
+    }
+
+    /// Numbers separated by whitespace, failing at the first word that isn't
+    /// one.
+    pub fn numbers<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        self.words().map(|word| word.number()).collect()
+    }
+}
+
+impl<'a> From<&'a str> for Cursor<'a> {
+    fn from(line: &'a str) -> Self {
+        Cursor::new(line)
+    }
+}
+
+/// Every integer in `text` in order, skipping whatever is between them. A
+/// `-` just before the digits makes the number negative, unless it comes
+/// straight after another number as in `3-5`. For unsigned `T` a negative
+/// number is an error, as is one that doesn't fit.
+pub fn ints<'a, T: FromStr>(text: impl Into<Cursor<'a>>) -> Result<Vec<T>, ParseError> {
+    let cursor = text.into();
+    let bytes = cursor.text.as_bytes();
+    let mut numbers = vec![];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+
+        let negative = index > 0
+            && bytes[index - 1] == b'-'
+            && (index < 2 || !bytes[index - 2].is_ascii_digit());
+        let start = if negative { index - 1 } else { index };
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+
+        numbers.push(cursor.part(&cursor.text[start..index]).number()?);
+    }
+
+    Ok(numbers)
+}
+
+/// Split at the first `delimiter`, or fail with `expected` about all of
+/// `text`.
+pub fn split_once_or_err<'a>(
+    text: impl Into<Cursor<'a>>,
+    delimiter: &str,
+    expected: &str,
+) -> Result<(Cursor<'a>, Cursor<'a>), ParseError> {
+    let cursor = text.into();
+
+    cursor
+        .text
+        .split_once(delimiter)
+        .map(|(left, right)| (cursor.part(left), cursor.part(right)))
+        .ok_or_else(|| cursor.error(expected))
+}
+
+/// A `key: value` pair.
+pub fn key_value<'a>(text: impl Into<Cursor<'a>>) -> Result<(Cursor<'a>, Cursor<'a>), ParseError> {
+    split_once_or_err(text, ": ", "expected '<key>: <value>'")
+}
+
+/// A `name = (left, right)` node.
+pub fn named_pair<'a>(
+    text: impl Into<Cursor<'a>>,
+) -> Result<(Cursor<'a>, (Cursor<'a>, Cursor<'a>)), ParseError> {
+    let (name, pair) = split_once_or_err(text, " = ", "expected '<name> = (<left>, <right>)'")?;
+    let pair = pair.strip_prefix("(")?.strip_suffix(")")?;
+    let (left, right) = split_once_or_err(pair, ", ", "expected '<left>, <right>'")?;
+
+    Ok((name, (left, right)))
+}
+
+/// Hands out the lines of some text one at a time, each as a [`Cursor`] that
+/// knows which line of the input it is.
+#[derive(Debug, Clone)]
+pub struct LineCursor<'a> {
+    lines: std::str::Split<'a, char>,
+    next: usize,
+}
+
+impl<'a> LineCursor<'a> {
+    /// The lines of `text`, which starts the input.
+    pub fn new(text: &'a str) -> LineCursor<'a> {
+        LineCursor::starting_at(text, 0)
+    }
+
+    /// The lines of `text`, which starts after `offset` lines of the input.
+    pub fn starting_at(text: &'a str, offset: usize) -> LineCursor<'a> {
+        LineCursor {
+            lines: text.split('\n'),
+            next: offset,
+        }
+    }
+
+    /// The next line, failing with what was `expected` if there isn't one.
+    pub fn next_line(&mut self, expected: &str) -> Result<Cursor<'a>, ParseError> {
+        self.next()
+            .ok_or_else(|| end_of_input(expected).shifted(self.next))
+    }
+
+    /// Fail if there are any lines left.
+    pub fn finish(mut self) -> Result<(), ParseError> {
+        match self.next() {
+            Some(line) => Err(line.error("expected no more lines")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for LineCursor<'a> {
+    fn from(text: &'a str) -> Self {
+        LineCursor::new(text)
+    }
+}
+
+impl<'a> Iterator for LineCursor<'a> {
+    type Item = Cursor<'a>;
+
+    fn next(&mut self) -> Option<Cursor<'a>> {
+        let line = self.lines.next()?;
+        let cursor = Cursor {
+            line,
+            text: line,
+            offset: self.next,
+        };
+        self.next += 1;
+
+        Some(cursor)
+    }
+}
+
+/// Hands out the blocks of an input one at a time.
+#[derive(Debug, Clone)]
+pub struct BlockCursor<'a> {
+    blocks: std::vec::IntoIter<Block<'a>>,
+    end: usize,
+}
+
+impl<'a> BlockCursor<'a> {
+    /// The blocks of `input`, separated by blank lines.
+    pub fn new(input: &'a PuzzleInput) -> BlockCursor<'a> {
+        let mut lines = input.lines();
+        lines.by_ref().for_each(drop);
+
+        BlockCursor {
+            blocks: input.blocks().into_iter(),
+            end: lines.next,
+        }
+    }
+
+    /// The next block, failing with what was `expected` if there isn't one.
+    pub fn next_block(&mut self, expected: &str) -> Result<Block<'a>, ParseError> {
+        self.next()
+            .ok_or_else(|| end_of_input(expected).shifted(self.end))
+    }
+
+    /// Fail if there are any blocks left.
+    pub fn finish(mut self) -> Result<(), ParseError> {
+        match self.next().and_then(|block| block.lines().next()) {
+            Some(line) => Err(line.error("expected no more blocks")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Iterator for BlockCursor<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Block<'a>> {
+        self.blocks.next()
+    }
+}
+
+/// An error for running out of input while expecting more, on the first
+/// line until a caller shifts it into place.
+fn end_of_input(expected: &str) -> ParseError {
+    ParseError::at("", "", format!("expected {expected}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(ints::<u32>("1 2  3 "), Ok(vec![1, 2, 3]));
+        assert_eq!(ints::<u32>("  1    2  3"), Ok(vec![1, 2, 3]));
+        assert_eq!(ints::<i64>("x=-4, y=10..-2"), Ok(vec![-4, 10, -2]));
+        assert_eq!(ints::<i32>("3-5"), Ok(vec![3, 5]));
+        assert_eq!(ints::<u8>("no numbers"), Ok(vec![]));
+
+        let error = ints::<u8>("a 1, b -2").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (8, "-2"));
+        assert_eq!(ints::<u8>("300").unwrap_err().message, "expected a number");
+    }
+
+    #[test]
+    fn test_cursor_keeps_columns() {
+        let line = "Game 12: 3 blue";
+        let (game, pulls) = key_value(line).unwrap();
+
+        assert_eq!(game.strip_prefix("Game ").unwrap().number(), Ok(12));
+        let error = pulls.words().nth(1).unwrap().number::<u8>().unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (12, "blue"));
+        assert_eq!(game.strip_prefix("Card").unwrap_err().column, 1);
+        assert_eq!(ints::<u8>(pulls), Ok(vec![3]));
+    }
+
+    #[test]
+    fn test_split_once_or_err() {
+        let (left, right) = split_once_or_err("32T3K 765", " ", "expected a hand").unwrap();
+        assert_eq!((left.as_str(), right.number()), ("32T3K", Ok(765)));
+
+        let error = split_once_or_err("32T3K", " ", "expected a hand").unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (1, "expected a hand")
+        );
+    }
+
+    #[test]
+    fn test_numbers_and_chars() {
+        let cursor = Cursor::new("1,,3 x");
+        let error = cursor
+            .split(",")
+            .map(|part| part.number::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(error.column, 3);
+
+        assert_eq!(Cursor::new("-1 2").numbers(), Ok(vec![-1, 2]));
+        assert_eq!(Cursor::new("1 2 x").numbers::<u8>().unwrap_err().column, 5);
+
+        let (c, at) = Cursor::new("ä#").chars().nth(1).unwrap();
+        assert_eq!((c, at.error("").column), ('#', 2));
+
+        let digits = Cursor::new("ä.123x")
+            .skip_chars(2)
+            .take_while(|c| c.is_ascii_digit());
+        let error = digits.error("");
+        assert_eq!((error.column, error.text.as_str()), (3, "123"));
+        assert!(Cursor::new("ab").skip_chars(5).is_empty());
+    }
+
+    #[test]
+    fn test_named_pair() {
+        let (name, (left, right)) = named_pair("AAA = (BBB, CCC)").unwrap();
+        assert_eq!(
+            (name.as_str(), left.as_str(), right.as_str()),
+            ("AAA", "BBB", "CCC")
+        );
+
+        assert_eq!(named_pair("AAA (BBB, CCC)").unwrap_err().column, 1);
+        assert_eq!(named_pair("AAA = BBB, CCC)").unwrap_err().column, 7);
+        assert_eq!(named_pair("AAA = (BBB CCC)").unwrap_err().column, 8);
+    }
+
+    #[test]
+    fn test_line_cursor() {
+        let mut lines = LineCursor::starting_at("1 2\nx", 3);
+
+        assert_eq!(
+            lines.next_line("numbers").unwrap().numbers(),
+            Ok(vec![1, 2])
+        );
+        let error = lines.clone().next().unwrap().number::<u8>().unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(lines.clone().finish().unwrap_err().line, 5);
+
+        lines.next();
+        let error = lines.next_line("a third line").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (6, "expected a third line")
+        );
+        assert_eq!(lines.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_block_cursor() {
+        let input = PuzzleInput::new("a\n\nb\nc\n\nd");
+        let mut blocks = BlockCursor::new(&input);
+
+        assert_eq!(blocks.next_block("a").unwrap().text, "a");
+        let mut lines = blocks.next_block("b").unwrap().lines();
+        lines.next();
+        assert_eq!(lines.next().unwrap().error("").line, 4);
+        assert_eq!(blocks.clone().finish().unwrap_err().line, 6);
+
+        blocks.next();
+        assert_eq!(blocks.next_block("more").unwrap_err().line, 7);
+
+        let input = PuzzleInput::new("\n\na");
+        let mut blocks = BlockCursor::new(&input);
+        assert_eq!(
+            blocks
+                .next_block("a")
+                .unwrap()
+                .lines()
+                .next()
+                .unwrap()
+                .error("")
+                .line,
+            3
+        );
+        assert_eq!(blocks.next_block("more").unwrap_err().line, 4);
+    }
+}